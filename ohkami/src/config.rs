@@ -0,0 +1,374 @@
+#![cfg(feature="__rt_native__")]
+
+use std::sync::{Arc, atomic::{AtomicUsize, Ordering}};
+use std::time::Duration;
+
+
+/// # Server configuration for `Ohkami::howl_with`
+///
+/// Limits and timeouts applied to each connection and request.
+/// Each `howl_with` call has its own configuration, so multiple
+/// servers in one binary can be configured independently.
+///
+/// <br>
+///
+/// | item | default |
+/// |:--|:--|
+/// | `keepalive_timeout`      | 42 seconds, or `OHKAMI_KEEPALIVE_TIMEOUT` env var |
+/// | `header_read_timeout`    | 30 seconds |
+/// | `body_read_timeout`      | 5 minutes |
+/// | `min_body_rate`          | none |
+/// | `response_write_timeout` | 30 seconds |
+/// | `header_buffer_size`     | 1 KiB |
+/// | `max_header_size`        | 16 KiB |
+/// | `payload_limit`          | 4 GiB ( `usize::MAX` on 32-bit targets ) |
+/// | `max_headers`            | 100 |
+/// | `strict_parsing`         | `true` |
+/// | `websocket_timeout`      | 1 hour, or `OHKAMI_WEBSOCKET_TIMEOUT` env var |
+/// | `max_connections`        | unlimited |
+/// | `reject_over_limit`      | `false` |
+/// | `proxy_protocol`         | `false` |
+/// | `on_io_error`            | logging to stderr |
+/// | `cancel_on_disconnect`   | `false` |
+/// | `shutdown_timeout`       | 30 seconds |
+/// | `max_concurrent_streams` | 100 |
+///
+/// <br>
+///
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::ServerConfig;
+/// use std::time::Duration;
+///
+/// #[tokio::main]
+/// async fn main() {
+///     let config = ServerConfig::new()
+///         .keepalive_timeout(Duration::from_secs(10))
+///         .payload_limit(1 << 20)
+///         .max_connections(1024);
+///
+///     Ohkami::new((
+///         "/".GET(|| async {"Hello, world!"}),
+///     )).howl_with(config, "0.0.0.0:8080").await
+/// }
+/// ```
+#[derive(Clone)]
+pub struct ServerConfig {
+    pub(crate) keepalive_timeout:      Duration,
+    pub(crate) header_read_timeout:    Duration,
+    pub(crate) body_read_timeout:      Duration,
+    pub(crate) min_body_rate:          Option<usize>,
+    pub(crate) response_write_timeout: Duration,
+    pub(crate) header_buffer_size:     usize,
+    pub(crate) max_header_size:        usize,
+    pub(crate) payload_limit:          usize,
+    pub(crate) max_headers:            usize,
+    pub(crate) strict_parsing:         bool,
+    #[cfg(feature="ws")]
+    pub(crate) websocket_timeout:      Duration,
+    pub(crate) max_connections:        Option<usize>,
+    pub(crate) reject_over_limit:      bool,
+    pub(crate) open_connections:       OpenConnections,
+    pub(crate) proxy_protocol:         bool,
+    pub(crate) io_error_hook:          Option<IoErrorHook>,
+    pub(crate) cancel_on_disconnect:   bool,
+    pub(crate) shutdown_timeout:       Duration,
+    #[cfg(feature="http2")]
+    pub(crate) max_concurrent_streams: usize,
+
+    #[cfg(feature="tls")]
+    pub(crate) tls:                    Option<crate::tls::TlsConfig>,
+}
+
+/// set by `on_io_error`
+type IoErrorHook = Arc<dyn Fn(&std::io::Error, &crate::PeerAddr) + Send + Sync>;
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        Self {
+            keepalive_timeout:      Duration::from_secs(env::OHKAMI_KEEPALIVE_TIMEOUT()),
+            header_read_timeout:    Duration::from_secs(30),
+            body_read_timeout:      Duration::from_secs(5 * 60),
+            min_body_rate:          None,
+            response_write_timeout: Duration::from_secs(30),
+            header_buffer_size:     1 << 10,
+            max_header_size:        1 << 14,
+            payload_limit:          usize::try_from(1_u64 << 32).unwrap_or(usize::MAX),
+            max_headers:            100,
+            strict_parsing:         true,
+            #[cfg(feature="ws")]
+            websocket_timeout:      Duration::from_secs(env::OHKAMI_WEBSOCKET_TIMEOUT()),
+            max_connections:        None,
+            reject_over_limit:      false,
+            open_connections:       OpenConnections::default(),
+            proxy_protocol:         false,
+            io_error_hook:          None,
+            cancel_on_disconnect:   false,
+            shutdown_timeout:       Duration::from_secs(30),
+            #[cfg(feature="http2")]
+            max_concurrent_streams: 100,
+
+            #[cfg(feature="tls")]
+            tls:                    None,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// How long an idle connection is kept open waiting for the next request.
+    /// The timer restarts after each response.
+    pub fn keepalive_timeout(mut self, timeout: Duration) -> Self {
+        self.keepalive_timeout = timeout;
+        self
+    }
+
+    /// Deadline for reading the request line and headers after the first bytes
+    /// of a request arrived, responding `408 Request Timeout` beyond it.
+    /// A PROXY header and a TLS handshake are bounded by this too.
+    pub fn header_read_timeout(mut self, timeout: Duration) -> Self {
+        self.header_read_timeout = timeout;
+        self
+    }
+
+    /// Deadline for reading the request body,
+    /// responding `408 Request Timeout` beyond it
+    pub fn body_read_timeout(mut self, timeout: Duration) -> Self {
+        self.body_read_timeout = timeout;
+        self
+    }
+
+    /// Minimum average rate ( bytes/sec ) at which a request body must arrive,
+    /// checked every second while reading it. Slower ones are responded
+    /// `408 Request Timeout` before `body_read_timeout`.
+    pub fn min_body_rate(mut self, bytes_per_sec: usize) -> Self {
+        self.min_body_rate = Some(bytes_per_sec);
+        self
+    }
+
+    /// Deadline for each write of a response to the connection ( each message
+    /// for a stream ). The connection is closed quietly beyond it, as the client
+    /// isn't reading. For HTTP/2, only the stream is reset.
+    pub fn response_write_timeout(mut self, timeout: Duration) -> Self {
+        self.response_write_timeout = timeout;
+        self
+    }
+
+    /// Initial size of the buffer the request line and headers are read into.
+    /// The buffer grows up to `max_header_size` and is reused for later requests
+    /// on the same connection.
+    pub fn header_buffer_size(mut self, size: usize) -> Self {
+        self.header_buffer_size = size;
+        self
+    }
+
+    /// Max total size of the request line and headers,
+    /// responding `431 Request Header Fields Too Large` beyond it
+    pub fn max_header_size(mut self, size: usize) -> Self {
+        self.max_header_size = size;
+        self
+    }
+
+    /// Max `Content-Length` to accept, responding `413 Payload Too Large` beyond it
+    pub fn payload_limit(mut self, limit: usize) -> Self {
+        self.payload_limit = limit;
+        self
+    }
+
+    /// Max number of header fields in a request,
+    /// responding `431 Request Header Fields Too Large` beyond it
+    pub fn max_headers(mut self, max: usize) -> Self {
+        self.max_headers = max;
+        self
+    }
+
+    /// Whether to reject malformed or ambiguous requests with `400 Bad Request`
+    /// following RFC 9112 : conflicting `Content-Length`s, `Transfer-Encoding`
+    /// together with `Content-Length`, multiple `Host`s, invalid header names,
+    /// control characters in request target or header values, and so on.
+    /// 
+    /// Enabled by default. Disabling it accepts some legacy clients' requests
+    /// such as duplicated but identical `Content-Length`s, at the cost of
+    /// exposure to request smuggling behind a proxy.
+    pub fn strict_parsing(mut self, strict: bool) -> Self {
+        self.strict_parsing = strict;
+        self
+    }
+
+    #[cfg(feature="ws")]
+    /// How long a WebSocket session is kept before closing
+    pub fn websocket_timeout(mut self, timeout: Duration) -> Self {
+        self.websocket_timeout = timeout;
+        self
+    }
+
+    /// Max number of connections served at once.
+    /// Accepting is paused while the limit is reached,
+    /// or see `reject_over_limit`.
+    pub fn max_connections(mut self, max: usize) -> Self {
+        self.max_connections = Some(max);
+        self
+    }
+
+    /// Whether to keep accepting while `max_connections` is reached,
+    /// responding `503 Service Unavailable` with `Connection: close`
+    /// to the connections over it, instead of pausing accepting.
+    /// 
+    /// Pausing leaves new clients waiting in the OS's backlog;
+    /// rejecting lets them know at once and retry elsewhere.
+    /// 
+    /// The `503` is sent without reading a PROXY header nor a TLS handshake
+    /// ( a TLS connection is just closed ), within `response_write_timeout`,
+    /// and at most `max_connections` connections are being rejected at once,
+    /// beyond which they're closed.
+    pub fn reject_over_limit(mut self, reject: bool) -> Self {
+        self.reject_over_limit = reject;
+        self
+    }
+
+    /// Handle to the number of connections currently served with this config
+    /// ( or its clones ), available while serving.
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// use ohkami::ServerConfig;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let config = ServerConfig::new().max_connections(1024);
+    ///     let open_connections = config.open_connections();
+    /// 
+    ///     Ohkami::new((
+    ///         "/metrics".GET(move || {
+    ///             let n = open_connections.get();
+    ///             async move {format!("open_connections {n}")}
+    ///         }),
+    ///     )).howl_with(config, "0.0.0.0:8080").await
+    /// }
+    /// ```
+    pub fn open_connections(&self) -> OpenConnections {
+        self.open_connections.clone()
+    }
+
+    /// Whether to require a PROXY protocol v1 or v2 header at the start of every
+    /// connection, as sent by AWS NLB or HAProxy with `send-proxy`. The client's
+    /// address in it is set to `Request::ip` and `Request::peer`, and the whole
+    /// header is available to handlers as `&ProxyInfo`.
+    /// 
+    /// Connections without a valid header ( within `header_read_timeout` ) are closed.
+    /// Enable only when every connection comes through such a proxy, otherwise
+    /// any client can claim whatever address it likes.
+    pub fn proxy_protocol(mut self, enable: bool) -> Self {
+        self.proxy_protocol = enable;
+        self
+    }
+
+    /// Report an I/O error on a connection by `hook` with the peer,
+    /// instead of logging it to stderr. The connection is closed after that.
+    /// 
+    /// The peer's disconnection ( reset, abort, broken pipe or unexpected EOF )
+    /// is not an error here, and just closes the connection quietly.
+    pub fn on_io_error(mut self, hook: impl Fn(&std::io::Error, &crate::PeerAddr) + Send + Sync + 'static) -> Self {
+        self.io_error_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Whether to drop the handler ( with fangs ) and close the connection when
+    /// the client disconnects before the response, instead of running it to
+    /// completion for nobody. Streaming responses are stopped likewise.
+    /// 
+    /// Either way, handlers and fangs can await `Disconnected` to notice it.
+    /// Only HTTP/1.1 connections are watched, and the client closing the
+    /// connection ( FIN ) after the request is taken as disconnected, as well as
+    /// a reset or broken one. So keep this `false` for clients half-closing
+    /// their writing side after the request and still waiting for the response.
+    /// 
+    /// This doesn't apply to HTTP/2: its handlers run to completion even if
+    /// the stream is reset or the connection is closed.
+    pub fn cancel_on_disconnect(mut self, cancel: bool) -> Self {
+        self.cancel_on_disconnect = cancel;
+        self
+    }
+
+    /// How long to wait for sessions to finish after the shutdown signal.
+    /// Keep-alive and WebSocket sessions still open beyond it are closed forcibly.
+    pub fn shutdown_timeout(mut self, timeout: Duration) -> Self {
+        self.shutdown_timeout = timeout;
+        self
+    }
+
+    #[cfg(feature="http2")]
+    /// Max number of streams served at once on each HTTP/2 connection.
+    /// The client is told this limit, and streams beyond it are refused.
+    pub fn max_concurrent_streams(mut self, max: usize) -> Self {
+        self.max_concurrent_streams = max;
+        self
+    }
+
+    #[cfg(feature="tls")]
+    /// Serve HTTPS with `tls_config`
+    pub fn tls(mut self, tls_config: crate::tls::TlsConfig) -> Self {
+        self.tls = Some(tls_config);
+        self
+    }
+}
+
+/// Number of open connections, obtained by `ServerConfig::open_connections`
+#[derive(Clone, Default)]
+pub struct OpenConnections(Arc<AtomicUsize>);
+
+impl OpenConnections {
+    pub fn get(&self) -> usize {
+        self.0.load(Ordering::Relaxed)
+    }
+
+    /// Count a connection while the returned guard is alive
+    pub(crate) fn open(&self) -> OpenConnection {
+        self.0.fetch_add(1, Ordering::Relaxed);
+        OpenConnection(self.0.clone())
+    }
+}
+
+impl std::fmt::Debug for OpenConnections {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.get().fmt(f)
+    }
+}
+
+pub(crate) struct OpenConnection(Arc<AtomicUsize>);
+
+impl Drop for OpenConnection {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+mod env {
+    #![allow(unused, non_snake_case)]
+
+    use std::sync::OnceLock;
+
+    pub(super) fn OHKAMI_KEEPALIVE_TIMEOUT() -> u64 {
+        static OHKAMI_KEEPALIVE_TIMEOUT: OnceLock<u64> = OnceLock::new();
+        *OHKAMI_KEEPALIVE_TIMEOUT.get_or_init(|| {
+            std::env::var("OHKAMI_KEEPALIVE_TIMEOUT").ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(42)
+        })
+    }
+
+    #[cfg(feature="ws")]
+    pub(super) fn OHKAMI_WEBSOCKET_TIMEOUT() -> u64 {
+        static OHKAMI_WEBSOCKET_TIMEOUT: OnceLock<u64> = OnceLock::new();
+        *OHKAMI_WEBSOCKET_TIMEOUT.get_or_init(|| {
+            std::env::var("OHKAMI_WEBSOCKET_TIMEOUT").ok()
+                .and_then(|v| v.parse().ok())
+                .unwrap_or(60 * 60)
+        })
+    }
+}
@@ -103,7 +103,7 @@ const _: () = {
     {
         let req = TestRequest::PUT("/greet/ohkami/1");
         let res = t.oneshot(req).await;
-        assert_eq!(res.status(), Status::NotFound);
+        assert_eq!(res.status(), Status::MethodNotAllowed);
     }
     {
         let req = TestRequest::GET("/greet/ohkami/1");
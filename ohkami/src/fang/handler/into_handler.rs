@@ -50,7 +50,7 @@ const _: (/* FromParam */) = {
     {
         fn into_handler(self) -> Handler {
             Handler::new(move |req|
-                match P1::from_raw_param(unsafe {req.path.assume_one_param()}) {
+                match P1::from_raw_param(unsafe {req.path.assume_params::<1>()[0]}) {
                     Ok(p1) => {
                         let res = self(p1);
                         Box::pin(async move {res.await.into_response()})
@@ -60,46 +60,6 @@ const _: (/* FromParam */) = {
             )
         }
     }
-
-    impl<'req, F, Body, Fut, P1:FromParam<'req>> IntoHandler<fn(((P1,),))->Body> for F
-    where
-        F:    Fn((P1,)) -> Fut + SendSyncOnNative + 'static,
-        Body: IntoResponse,
-        Fut:  Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req|
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed once before this code
-                match P1::from_raw_param(unsafe {req.path.assume_one_param()}) {
-                    Ok(p1) => {
-                        let res = self((p1,));
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    Err(e) => __error__(e)
-                }
-            )
-        }
-    }
-
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, P2:FromParam<'req>> IntoHandler<fn(((P1, P2),))->Body> for F
-    where
-        F:   Fn((P1, P2)) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                let (p1, p2) = unsafe {req.path.assume_two_params()};
-                match (P1::from_raw_param(p1), P2::from_raw_param(p2)) {
-                    (Ok(p1), Ok(p2)) => {
-                        let res = self((p1, p2));
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e), _) | (_, Err(e)) => __error__(e),
-                }
-            })
-        }
-    }
 };
 
 const _: (/* FromRequest items */) = {
@@ -117,7 +77,7 @@ const _: (/* FromRequest items */) = {
                     }
                     Err(e) => __error__(e)
                 }
-            )
+            ).requiring([Item1::required_path_params()])
         }
     }
 
@@ -136,7 +96,7 @@ const _: (/* FromRequest items */) = {
                     (Err(e), _) |
                     (_, Err(e)) => __error__(e),
                 }
-            )
+            ).requiring([Item1::required_path_params(), Item2::required_path_params()])
         }
     }
 
@@ -156,7 +116,7 @@ const _: (/* FromRequest items */) = {
                     (_, Err(e), _) |
                     (_, _, Err(e)) => __error__(e),
                 }
-            )
+            ).requiring([Item1::required_path_params(), Item2::required_path_params(), Item3::required_path_params()])
         }
     }
 
@@ -177,7 +137,7 @@ const _: (/* FromRequest items */) = {
                     (_, _, Err(e),_) |
                     (_,_, _, Err(e)) => __error__(e),
                 }
-            )
+            ).requiring([Item1::required_path_params(), Item2::required_path_params(), Item3::required_path_params(), Item4::required_path_params()])
         }
     }
 };
@@ -192,7 +152,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
             Handler::new(move |req| {
                 // SAFETY: Due to the architecture of `Router`,
                 // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
+                let p1 = unsafe {req.path.assume_params::<1>()[0]};
 
                 match (P1::from_raw_param(p1), from_request(req)) {
                     (Ok(p1), Ok(item1)) => {
@@ -202,7 +162,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                     (Err(e), _) |
                     (_, Err(e)) => __error__(e),
                 }
-            })
+            }).requiring([Item1::required_path_params()])
         }
     }
 
@@ -215,7 +175,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
             Handler::new(move |req| {
                 // SAFETY: Due to the architecture of `Router`,
                 // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
+                let p1 = unsafe {req.path.assume_params::<1>()[0]};
 
                 match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2)) => {
@@ -226,7 +186,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                     (_,Err(e),_) |
                     (_,_,Err(e)) => __error__(e),
                 }
-            })
+            }).requiring([Item1::required_path_params(), Item2::required_path_params()])
         }
     }
 
@@ -239,7 +199,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
             Handler::new(move |req| {
                 // SAFETY: Due to the architecture of `Router`,
                 // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
+                let p1 = unsafe {req.path.assume_params::<1>()[0]};
 
                 match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2), Ok(item3)) => {
@@ -251,7 +211,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                     (_,_,Err(e),_) |
                     (_,_,_,Err(e)) => __error__(e),
                 }
-            })
+            }).requiring([Item1::required_path_params(), Item2::required_path_params(), Item3::required_path_params()])
         }
     }
 
@@ -264,7 +224,7 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
             Handler::new(move |req| {
                 // SAFETY: Due to the architecture of `Router`,
                 // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
+                let p1 = unsafe {req.path.assume_params::<1>()[0]};
 
                 match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
                     (Ok(p1), Ok(item1), Ok(item2), Ok(item3), Ok(item4)) => {
@@ -277,216 +237,87 @@ const _: (/* one FromParam without tuple and FromRequest items */) = {
                     (_,_,_,Err(e),_) |
                     (_,_,_,_,Err(e)) => __error__(e),
                 }
-            })
+            }).requiring([Item1::required_path_params(), Item2::required_path_params(), Item3::required_path_params(), Item4::required_path_params()])
         }
     }
 };
 
-const _: (/* one FromParam and FromRequest items */) = {
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, Item1:FromRequest<'req>> IntoHandler<fn((P1,), Item1)->Body> for F
-    where
-        F:   Fn((P1,), Item1) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
 
-                match (P1::from_raw_param(p1), from_request::<Item1>(req)) {
-                    (Ok(p1), Ok(item1)) => {
-                        let res = self((p1,), item1);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_) |
-                    (_,Err(e)) => __error__(e),
-                }
-            })
-        }
-    }
-
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, Item1:FromRequest<'req>, Item2:FromRequest<'req>> IntoHandler<fn((P1,), Item1, Item2)->Body> for F
-    where
-        F:   Fn((P1,), Item1, Item2) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
-
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req)) {
-                    (Ok(p1), Ok(item1), Ok(item2)) => {
-                        let res = self((p1,), item1, item2);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_) |
-                    (_,Err(e),_) |
-                    (_,_,Err(e)) => __error__(e),
+const _: (/* tuple of FromParams and FromRequest items */) = {
+    macro_rules! tuple_params_handler {
+        ( $( $P:ident $p:ident ),+ ) => {
+            impl<'req, F, Fut, Body:IntoResponse, $($P:FromParam<'req>),+> IntoHandler<fn((($($P,)+),))->Body> for F
+            where
+                F:   Fn(($($P,)+)) -> Fut + SendSyncOnNative + 'static,
+                Fut: Future<Output = Body> + SendOnNative + 'static,
+            {
+                fn into_handler(self) -> Handler {
+                    Handler::new(move |req| {
+                        // SAFETY: Due to the architecture of `Router`,
+                        // `params` has already been set as many as the tuple's arity before this code
+                        let [$($p),+] = unsafe {req.path.assume_params()};
+
+                        match (|| Ok::<_, Box<Response>>(($($P::from_raw_param($p)?,)+)))() {
+                            Ok(params) => {
+                                let res = self(params);
+                                Box::pin(async move {res.await.into_response()})
+                            }
+                            Err(e) => __error__(*e)
+                        }
+                    })
                 }
-            })
-        }
-    }
-
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, Item1:FromRequest<'req>, Item2:FromRequest<'req>, Item3:FromRequest<'req>> IntoHandler<fn((P1,), Item1, Item2, Item3)->Body> for F
-    where
-        F:   Fn((P1,), Item1, Item2, Item3) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
-                
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
-                    (Ok(p1), Ok(item1), Ok(item2), Ok(item3)) => {
-                        let res = self((p1,), item1, item2, item3);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_,_) |
-                    (_,Err(e),_,_) |
-                    (_,_,Err(e),_) |
-                    (_,_,_,Err(e)) => __error__(e),
-                }
-            })
-        }
-    }
-
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, Item1:FromRequest<'req>, Item2:FromRequest<'req>, Item3:FromRequest<'req>, Item4:FromRequest<'req>> IntoHandler<fn((P1,), Item1, Item2, Item3, Item4)->Body> for F
-    where
-        F:   Fn((P1,), Item1, Item2, Item3, Item4) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed once before this code
-                let p1 = unsafe {req.path.assume_one_param()};
-                
-                match (P1::from_raw_param(p1), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
-                    (Ok(p1), Ok(item1), Ok(item2), Ok(item3), Ok(item4)) => {
-                        let res = self((p1,), item1, item2, item3, item4);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_,_,_) |
-                    (_,Err(e),_,_,_) |
-                    (_,_,Err(e),_,_) |
-                    (_,_,_,Err(e),_) |
-                    (_,_,_,_,Err(e)) => __error__(e),
-                }
-            })
-        }
-    }
-};
-
-const _: (/* two PathParams and FromRequest items */) = {
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, P2:FromParam<'req>, Item1:FromRequest<'req>> IntoHandler<fn((P1, P2), Item1)->Body> for F
-    where
-        F:   Fn((P1, P2), Item1) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed twice before this code
-                let (p1, p2) = unsafe {req.path.assume_two_params()};
-
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req)) {
-                    (Ok(p1), Ok(p2), Ok(item1)) => {
-                        let res = self((p1, p2), item1); 
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_) |
-                    (_,Err(e),_) |
-                    (_,_,Err(e)) => __error__(e),
-                }
-            })
-        }
-    }
-
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, P2:FromParam<'req>, Item1:FromRequest<'req>, Item2:FromRequest<'req>> IntoHandler<fn((P1, P2), Item1, Item2)->Body> for F
-    where
-        F:   Fn((P1, P2), Item1, Item2) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed twice before this code
-                let (p1, p2) = unsafe {req.path.assume_two_params()};
-
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req), from_request::<Item2>(req)) {
-                    (Ok(p1), Ok(p2), Ok(item1), Ok(item2)) => {
-                        let res = self((p1, p2), item1, item2);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_,_) |
-                    (_,Err(e),_,_) |
-                    (_,_,Err(e),_) |
-                    (_,_,_,Err(e)) => __error__(e),
+            }
+        };
+        ( $( $P:ident $p:ident ),+ ; $( $Item:ident $item:ident ),+ ) => {
+            impl<'req, F, Fut, Body:IntoResponse, $($P:FromParam<'req>,)+ $($Item:FromRequest<'req>),+> IntoHandler<fn(($($P,)+), $($Item),+)->Body> for F
+            where
+                F:   Fn(($($P,)+), $($Item),+) -> Fut + SendSyncOnNative + 'static,
+                Fut: Future<Output = Body> + SendOnNative + 'static,
+            {
+                fn into_handler(self) -> Handler {
+                    Handler::new(move |req| {
+                        // SAFETY: Due to the architecture of `Router`,
+                        // `params` has already been set as many as the tuple's arity before this code
+                        let [$($p),+] = unsafe {req.path.assume_params()};
+
+                        match (|| Ok::<_, Box<Response>>((
+                            ($($P::from_raw_param($p)?,)+),
+                            $(from_request::<$Item>(req)?,)+
+                        )))() {
+                            Ok((params, $($item,)+)) => {
+                                let res = self(params, $($item),+);
+                                Box::pin(async move {res.await.into_response()})
+                            }
+                            Err(e) => __error__(*e)
+                        }
+                    }).requiring([$($Item::required_path_params()),+])
                 }
-            })
-        }
+            }
+        };
     }
 
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, P2:FromParam<'req>, Item1:FromRequest<'req>, Item2:FromRequest<'req>, Item3:FromRequest<'req>> IntoHandler<fn((P1, P2), Item1, Item2, Item3)->Body> for F
-    where
-        F:   Fn((P1, P2), Item1, Item2, Item3) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed twice before this code
-                let (p1, p2) = unsafe {req.path.assume_two_params()};
-
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req)) {
-                    (Ok(p1), Ok(p2), Ok(item1), Ok(item2), Ok(item3)) => {
-                        let res = self((p1, p2), item1, item2, item3);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_,_,_) |
-                    (_,Err(e),_,_,_) |
-                    (_,_,Err(e),_,_) |
-                    (_,_,_,Err(e),_) |
-                    (_,_,_,_,Err(e)) => __error__(e),
-                }
-            })
-        }
+    macro_rules! tuple_params_handlers {
+        ( $( ( $( $P:ident $p:ident ),+ ) )* ) => {$(
+            tuple_params_handler!($($P $p),+);
+            tuple_params_handler!($($P $p),+; Item1 item1);
+            tuple_params_handler!($($P $p),+; Item1 item1, Item2 item2);
+            tuple_params_handler!($($P $p),+; Item1 item1, Item2 item2, Item3 item3);
+            tuple_params_handler!($($P $p),+; Item1 item1, Item2 item2, Item3 item3, Item4 item4);
+        )*};
     }
 
-    impl<'req, F, Fut, Body:IntoResponse, P1:FromParam<'req>, P2:FromParam<'req>, Item1:FromRequest<'req>, Item2:FromRequest<'req>, Item3:FromRequest<'req>, Item4:FromRequest<'req>> IntoHandler<fn((P1, P2), Item1, Item2, Item3, Item4)->Body> for F
-    where
-        F:   Fn((P1, P2), Item1, Item2, Item3, Item4) -> Fut + SendSyncOnNative + 'static,
-        Fut: Future<Output = Body> + SendOnNative + 'static,
-    {
-        fn into_handler(self) -> Handler {
-            Handler::new(move |req| {
-                // SAFETY: Due to the architecture of `Router`,
-                // `params` has already `append`ed twice before this code
-                let (p1, p2) = unsafe {req.path.assume_two_params()};
-
-                match (FromParam::from_raw_param(p1), FromParam::from_raw_param(p2), from_request::<Item1>(req), from_request::<Item2>(req), from_request::<Item3>(req), from_request::<Item4>(req)) {
-                    (Ok(p1), Ok(p2), Ok(item1), Ok(item2), Ok(item3), Ok(item4)) => {
-                        let res = self((p1, p2), item1, item2, item3, item4);
-                        Box::pin(async move {res.await.into_response()})
-                    }
-                    (Err(e),_,_,_,_,_) |
-                    (_,Err(e),_,_,_,_) |
-                    (_,_,Err(e),_,_,_) |
-                    (_,_,_,Err(e),_,_) |
-                    (_,_,_,_,Err(e),_) |
-                    (_,_,_,_,_,Err(e)) => __error__(e),
-                }
-            })
-        }
+    tuple_params_handlers! {
+        (P1 p1)
+        (P1 p1, P2 p2)
+        (P1 p1, P2 p2, P3 p3)
+        (P1 p1, P2 p2, P3 p3, P4 p4)
+        (P1 p1, P2 p2, P3 p3, P4 p4, P5 p5)
+        (P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6)
+        (P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6, P7 p7)
+        (P1 p1, P2 p2, P3 p3, P4 p4, P5 p5, P6 p6, P7 p7, P8 p8)
     }
 };
 
-
 #[cfg(test)] #[test] fn handler_args() {
     async fn h0() -> &'static str {""}
 
@@ -8,12 +8,18 @@ use std::{pin::Pin, future::Future};
 
 
 #[derive(Clone)]
-pub struct Handler(BoxedFPC);
+pub struct Handler {
+    proc: BoxedFPC,
+    /// names of the path params passed to this handler, in order
+    pub(crate) param_names:     Vec<&'static str>,
+    /// names of the path params this handler's extractors require
+    pub(crate) required_params: Vec<&'static str>,
+}
 
 const _: () = {
     impl Into<BoxedFPC> for Handler {
         fn into(self) -> BoxedFPC {
-            self.0
+            self.proc
         }
     }
 
@@ -50,7 +56,30 @@ impl Handler {
             }
         };
 
-        Self(BoxedFPC::from_proc(HandlerProc(proc)))
+        Self {
+            proc:            BoxedFPC::from_proc(HandlerProc(proc)),
+            param_names:     Vec::new(),
+            required_params: Vec::new(),
+        }
+    }
+
+    /// Let the handler require, at route registration, path params
+    /// named by `FromRequest::required_path_params` of its extractors
+    pub(crate) fn requiring<const N: usize>(mut self, required: [Option<&'static [&'static str]>; N]) -> Self {
+        self.required_params.extend(required.into_iter().flatten().flatten());
+        self
+    }
+
+    /// Check, once the whole route is known, that all the required
+    /// path params are in it
+    pub(crate) fn validate_params(&self) -> Result<(), String> {
+        match self.required_params.iter().find(|r| !self.param_names.contains(r)) {
+            None          => Ok(()),
+            Some(missing) => Err(format!(
+                "Handler requires a path param `{missing}`, but the route has only {:?}",
+                self.param_names
+            ))
+        }
     }
 }
 
@@ -71,6 +100,6 @@ impl Handler {
             not_found.into_handler()
         });
 
-        Handler((&*NOT_FOUND).0.clone())
+        NOT_FOUND.clone()
     }
 }
@@ -16,6 +16,9 @@ pub use html::HTML;
 mod query;
 pub use query::Query;
 
+mod path;
+pub use path::Path;
+
 
 #[cold] #[inline(never)]
 fn reject(msg: impl std::fmt::Display) -> crate::Response {
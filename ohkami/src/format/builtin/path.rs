@@ -0,0 +1,157 @@
+use crate::{Response, FromRequest};
+use serde::{Deserialize, Deserializer, de::{self, IntoDeserializer, Visitor}};
+use std::borrow::Cow;
+
+
+/// Path params deserialized by their names in the route
+///
+/// <br>
+///
+/// *example.rs*
+/// ```
+/// use ohkami::prelude::*;
+/// use ohkami::format::Path;
+///
+/// #[derive(Deserialize)]
+/// struct PostPath<'req> {
+///     user_id: u64,
+///     post_id: &'req str,
+/// }
+///
+/// async fn get_post(
+///     Path(path): Path<PostPath<'_>>,
+/// ) -> String {
+///     format!("post {} of user #{}", path.post_id, path.user_id)
+/// }
+///
+/// # fn _o() -> Ohkami {
+/// Ohkami::new((
+///     "/users/:user_id/posts/:post_id".GET(get_post),
+/// ))
+/// # }
+/// ```
+///
+/// Fields not found in the route are reported as a panic
+/// when the `Ohkami` starts, or in `.test()`.
+pub struct Path<Schema>(pub Schema);
+
+impl<'req, S: Deserialize<'req>> FromRequest<'req> for Path<S> {
+    type Error = Response;
+
+    fn from_request(req: &'req crate::Request) -> Option<Result<Self, Self::Error>> {
+        let params = match req.path.named_params()
+            .map(|(name, param)| param.map(|param| (name, Param(param))))
+            .collect::<Result<Vec<_>, _>>()
+        {
+            Ok(params) => params,
+            Err(e) => return Some(Err(super::reject(format_args!("Invalid path param: {e}")))),
+        };
+        S::deserialize(de::value::MapDeserializer::<_, de::value::Error>::new(params.into_iter()))
+            .map_err(super::reject)
+            .map(Path).into()
+    }
+
+    fn required_path_params() -> Option<&'static [&'static str]> {
+        match S::deserialize(FieldNames) {
+            Err(Fields(fields)) => fields,
+            Ok(_) => None,
+        }
+    }
+}
+
+/// A path param, parsed on demand into the requested type
+struct Param<'req>(Cow<'req, str>);
+const _: () = {
+    impl<'req> IntoDeserializer<'req, de::value::Error> for Param<'req> {
+        type Deserializer = Self;
+        fn into_deserializer(self) -> Self::Deserializer {
+            self
+        }
+    }
+
+    macro_rules! parse {
+        ($( $deserialize:ident => $visit:ident ),*) => {$(
+            fn $deserialize<V: Visitor<'req>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+                visitor.$visit(self.0.parse().map_err(de::Error::custom)?)
+            }
+        )*};
+    }
+
+    impl<'req> Deserializer<'req> for Param<'req> {
+        type Error = de::value::Error;
+
+        fn deserialize_any<V: Visitor<'req>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            match self.0 {
+                Cow::Borrowed(s) => visitor.visit_borrowed_str(s),
+                Cow::Owned(s)    => visitor.visit_string(s),
+            }
+        }
+
+        parse! {
+            deserialize_bool => visit_bool,
+            deserialize_char => visit_char,
+            deserialize_i8   => visit_i8,
+            deserialize_i16  => visit_i16,
+            deserialize_i32  => visit_i32,
+            deserialize_i64  => visit_i64,
+            deserialize_i128 => visit_i128,
+            deserialize_u8   => visit_u8,
+            deserialize_u16  => visit_u16,
+            deserialize_u32  => visit_u32,
+            deserialize_u64  => visit_u64,
+            deserialize_u128 => visit_u128,
+            deserialize_f32  => visit_f32,
+            deserialize_f64  => visit_f64
+        }
+
+        fn deserialize_option<V: Visitor<'req>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_some(self)
+        }
+        fn deserialize_newtype_struct<V: Visitor<'req>>(self, _name: &'static str, visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_newtype_struct(self)
+        }
+        fn deserialize_enum<V: Visitor<'req>>(self, _name: &'static str, _variants: &'static [&'static str], visitor: V) -> Result<V::Value, Self::Error> {
+            visitor.visit_enum(self.0.into_deserializer())
+        }
+
+        serde::forward_to_deserialize_any! {<V: Visitor<'req>>
+            str string bytes byte_buf unit unit_struct seq tuple
+            tuple_struct map struct identifier ignored_any
+        }
+    }
+};
+
+/// Deserializer only to get the field names of a struct
+struct FieldNames;
+#[derive(Debug)]
+struct Fields(Option<&'static [&'static str]>);
+const _: () = {
+    impl std::fmt::Display for Fields {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl std::error::Error for Fields {}
+    impl de::Error for Fields {
+        fn custom<T: std::fmt::Display>(_: T) -> Self {
+            Self(None)
+        }
+    }
+
+    impl<'de> Deserializer<'de> for FieldNames {
+        type Error = Fields;
+
+        fn deserialize_any<V: Visitor<'de>>(self, _: V) -> Result<V::Value, Self::Error> {
+            Err(Fields(None))
+        }
+        fn deserialize_struct<V: Visitor<'de>>(self, _name: &'static str, fields: &'static [&'static str], _: V) -> Result<V::Value, Self::Error> {
+            Err(Fields(Some(fields)))
+        }
+
+        serde::forward_to_deserialize_any! {
+            bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 f32 f64 char str string
+            bytes byte_buf option unit unit_struct newtype_struct seq tuple
+            tuple_struct map enum identifier ignored_any
+        }
+    }
+};
@@ -3,6 +3,7 @@
 //! ## Builtin
 //! 
 //! - `Query` - query parameters
+//! - `Path` - path parameters by their names
 //! - `JSON` - payload of application/json
 //! - `Multipart` - payload of multipart/form-data
 //! - `URLEncoded` - payload of application/x-www-form-urlencoded
@@ -49,6 +49,18 @@ compile_error! {"
     (We recommend to touch `.cargo/config.toml`: `[build] target = \"wasm32-unknown-unknown\"`)
 "}
 
+#[cfg(all(feature="tls", feature="rt_worker"))]
+compile_error! {"
+    `tls` is not available with `rt_worker`!
+    (Cloudflare Workers terminates TLS in front of your worker)
+"}
+
+#[cfg(all(feature="http2", feature="rt_worker"))]
+compile_error! {"
+    `http2` is not available with `rt_worker`!
+    (Cloudflare Workers serves HTTP/2 in front of your worker)
+"}
+
 
 #[allow(unused)]
 mod __rt__ {
@@ -67,6 +79,18 @@ mod __rt__ {
     #[cfg(feature="rt_glommio")]
     pub(crate) use {glommio::net::{TcpListener, TcpStream}, std::net::ToSocketAddrs};
 
+    #[cfg(all(unix, feature="rt_tokio"))]
+    pub(crate) use tokio::net::{UnixListener, UnixStream};
+    #[cfg(all(unix, feature="rt_async-std"))]
+    pub(crate) use async_std::os::unix::net::{UnixListener, UnixStream};
+    #[cfg(all(unix, feature="rt_smol"))]
+    pub(crate) use smol::net::unix::{UnixListener, UnixStream};
+    #[cfg(all(unix, feature="rt_glommio"))]
+    pub(crate) use glommio::net::{UnixListener, UnixStream};
+
+    #[cfg(feature="tls")]
+    pub(crate) use crate::tls::{TlsAcceptor, TlsStream};
+
     #[cfg(feature="rt_tokio")]
     pub(crate) use tokio::task::spawn;
     #[cfg(feature="rt_async-std")]
@@ -118,6 +142,8 @@ mod __rt__ {
 
 mod request;
 pub use request::{Request, Method, FromRequest, FromParam};
+#[cfg(feature="__rt_native__")]
+pub use request::{PeerAddr, ProxyInfo, Disconnected};
 pub use ::ohkami_macros::FromRequest;
 
 mod response;
@@ -128,6 +154,11 @@ pub use fang::{Fang, FangProc};
 
 pub mod format;
 
+#[cfg(feature="__rt_native__")]
+mod config;
+#[cfg(feature="__rt_native__")]
+pub use config::{ServerConfig, OpenConnections};
+
 #[cfg(feature="__rt_native__")]
 mod session;
 #[cfg(feature="__rt_native__")]
@@ -136,7 +167,9 @@ use session::Session;
 #[cfg(feature="__rt__")]
 mod ohkami;
 #[cfg(feature="__rt__")]
-pub use ohkami::{Ohkami, Route};
+pub use ohkami::{Ohkami, Route, Panic};
+#[cfg(feature="__rt_native__")]
+pub use ohkami::Listener;
 
 pub mod header;
 
@@ -145,6 +178,9 @@ pub mod typed;
 #[cfg(all(feature="ws", feature="__rt_native__"))]
 pub mod ws;
 
+#[cfg(all(feature="tls", feature="__rt_native__"))]
+pub mod tls;
+
 #[cfg(feature="testing")]
 #[cfg(feature="__rt__")]
 pub mod testing;
@@ -121,11 +121,12 @@ fn my_ohkami() -> Ohkami {
 
     let req = TestRequest::GET("/api/profiles/the_user/follow");
     let res = t.oneshot(req).await;
-    assert_eq!(res.status(), Status::NotFound);
+    assert_eq!(res.status(), Status::MethodNotAllowed);
+    assert_eq!(res.header("Allow"), Some("POST, DELETE, OPTIONS"));
 
     let req = TestRequest::POST("/api/profiles/the_user");
     let res = t.oneshot(req).await;
-    assert_eq!(res.status(), Status::NotFound);
+    assert_eq!(res.status(), Status::MethodNotAllowed);
 
     let req = TestRequest::POST("/api/profiles/the_user/follow");
     let res = t.oneshot(req).await;
@@ -490,3 +491,315 @@ async fn prefixy_routes() {
         assert_eq!(res.status(), Status::NotFound);
     }
 }
+
+#[__rt__::test]
+async fn wildcard_routes() {
+    let t = Ohkami::new((
+        "/".GET(|| async {"index"}),
+        "/abc".GET(|| async {"This is abc"}),
+        "/api/users/:id".GET(|id: usize| async move {format!("user #{id}")}),
+        "/assets/*path".GET(|path: String| async move {format!("asset {path}")}),
+        "/*path".GET(|path: String| async move {format!("fallback {path}")}),
+    )).test(); {
+        let req = TestRequest::GET("/assets/css/app.css");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("asset css/app.css"));
+    } {
+        let req = TestRequest::GET("/assets/app%20v2.js");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("asset app v2.js"));
+    } {
+        let req = TestRequest::GET("/abc");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("This is abc"));
+    } {
+        let req = TestRequest::GET("/api/users/42");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("user #42"));
+    } {
+        let req = TestRequest::GET("/");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("index"));
+    } {
+        let req = TestRequest::GET("/abc2");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("fallback abc2"));
+    } {
+        let req = TestRequest::GET("/api/users");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("fallback api/users"));
+    } {
+        let req = TestRequest::GET("/api/users/42/profile");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("fallback api/users/42/profile"));
+    } {
+        let req = TestRequest::GET("/assets");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("fallback assets"));
+    } {
+        let req = TestRequest::POST("/assets/app.js");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::MethodNotAllowed);
+    }
+
+    let t = Ohkami::new((
+        "/users/:id".GET(|id: String| async move {format!("user {id}")}),
+        "/users/:id/*rest".GET(|(id, rest): (String, String)| async move {format!("user {id}: {rest}")}),
+    )).test(); {
+        let req = TestRequest::GET("/users/42");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("user 42"));
+    } {
+        let req = TestRequest::GET("/users/42/posts/7");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("user 42: posts/7"));
+    }
+}
+
+#[test]
+#[should_panic = "Wildcard section must be the last one"]
+fn wildcard_not_at_last() {
+    let _ = "/assets/*path/info".GET(|| async {""});
+}
+
+#[__rt__::test]
+async fn many_path_params() {
+    async fn eight_params(
+        (a, b, c, d, e, f, g, h): (&str, &str, &str, &str, &str, &str, &str, &str),
+        req: &Request,
+    ) -> String {
+        format!("{} {a}{b}{c}{d}{e}{f}{g} {h}", req.method)
+    }
+
+    let t = Ohkami::new((
+        "/:a/:b/:c/:d/:e/:f".GET(|(a, b, c, d, e, f): (u8, u8, u8, u8, u8, u8)| async move {
+            format!("{}", a + b + c + d + e + f)
+        }),
+        "/:a/:b/:c/:d/:e/:f/:g/*h".GET(eight_params),
+        "/:a/:b/:c/:d/:e/static".GET(|(a, b, c, d, e): (String, String, String, String, String)| async move {
+            format!("{a}{b}{c}{d}{e}")
+        }),
+    )).test(); {
+        let req = TestRequest::GET("/1/2/3/4/5/6");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("21"));
+    } {
+        let req = TestRequest::GET("/1/2/3/4/5/x");
+        let res = t.oneshot(req).await;
+        assert_ne!(res.status(), Status::OK);
+    } {
+        let req = TestRequest::GET("/a/b/c/d/e/static");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("abcde"));
+    } {
+        let req = TestRequest::GET("/a/b/c/d/e/f/g/h/i");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("GET abcdefg h/i"));
+    }
+}
+
+#[__rt__::test]
+async fn named_path_params() {
+    use crate::format::Path;
+
+    #[derive(::serde::Deserialize)]
+    struct PostPath<'req> {
+        user_id: u64,
+        post_id: &'req str,
+    }
+    async fn get_post(Path(path): Path<PostPath<'_>>) -> String {
+        format!("post {} of user #{}", path.post_id, path.user_id)
+    }
+
+    #[derive(::serde::Deserialize)]
+    struct CommentPath {
+        user_id: u64,
+        #[serde(rename = "comment")]
+        comment_id: String,
+    }
+    async fn get_comment(Path(path): Path<CommentPath>, req: &Request) -> String {
+        format!("{} comment {} of user #{}", req.method, path.comment_id, path.user_id)
+    }
+
+    let t = Ohkami::new((
+        "/users/:user_id/posts/:post_id".GET(get_post),
+        "/users/:user_id".By(Ohkami::new((
+            "/comments/*comment".GET(get_comment),
+        ))),
+    )).test(); {
+        let req = TestRequest::GET("/users/42/posts/hello");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("post hello of user #42"));
+    } {
+        let req = TestRequest::GET("/users/abc/posts/hello");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::BadRequest);
+    } {
+        let req = TestRequest::GET("/users/42/comments/a/b");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("GET comment a/b of user #42"));
+    } {
+        let req = TestRequest::GET("/users/42/comments/hello%20world");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("GET comment hello world of user #42"));
+    } {
+        /* not UTF-8 after percent-decoding */
+        let req = TestRequest::GET("/users/42/posts/%FF");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::BadRequest);
+    }
+}
+
+#[test]
+#[should_panic = "Handler requires a path param `post_id`"]
+fn named_path_params_not_in_route() {
+    use crate::format::Path;
+
+    #[derive(::serde::Deserialize)]
+    struct PostPath {
+        #[allow(unused)] user_id: u64,
+        #[allow(unused)] post_id: u64,
+    }
+
+    let _ = Ohkami::new((
+        "/users/:user_id/posts/:id".GET(|_: Path<PostPath>| async {""}),
+    )).test();
+}
+
+#[__rt__::test]
+async fn method_not_allowed_and_options() {
+    #[derive(Clone)]
+    struct Server;
+    impl FangAction for Server {
+        async fn back<'a>(&'a self, res: &'a mut Response) {
+            res.headers.set().Server("ohkami");
+        }
+    }
+
+    fn routes() -> Ohkami {
+        Ohkami::with(Server, (
+            "/users"
+                .GET(|| async {"list"})
+                .POST(|| async {"created"}),
+            "/users/:id"
+                .DELETE(|_id: usize| async {"deleted"}),
+        ))
+    }
+
+    let t = routes().test(); {
+        let req = TestRequest::PUT("/users");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::MethodNotAllowed);
+        assert_eq!(res.header("Allow"), Some("GET, POST, HEAD, OPTIONS"));
+        assert_eq!(res.header("Server"), Some("ohkami"));
+    } {
+        let req = TestRequest::HEAD("/users/42");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::MethodNotAllowed);
+        assert_eq!(res.header("Allow"), Some("DELETE, OPTIONS"));
+    } {
+        let req = TestRequest::PUT("/posts");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotFound);
+        assert_eq!(res.header("Allow"), None);
+    } {
+        let req = TestRequest::OPTIONS("/users");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotFound);
+    }
+
+    let t = routes().answer_options().test(); {
+        let req = TestRequest::OPTIONS("/users");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NoContent);
+        assert_eq!(res.header("Allow"), Some("GET, POST, HEAD, OPTIONS"));
+        assert_eq!(res.header("Server"), Some("ohkami"));
+    } {
+        let req = TestRequest::OPTIONS("/users/42");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NoContent);
+        assert_eq!(res.header("Allow"), Some("DELETE, OPTIONS"));
+    } {
+        let req = TestRequest::OPTIONS("/posts");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotFound);
+    } {
+        let req = TestRequest::OPTIONS("/users")
+            .header("Access-Control-Request-Method", "POST");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotImplemented);
+        assert_eq!(res.header("Allow"), None);
+    }
+}
+
+#[__rt__::test]
+async fn extension_methods() {
+    let t = Ohkami::new((
+        "/dav"
+            .GET(|| async {"get"})
+            .method("PROPFIND", |req: &Request| {
+                let method = req.method;
+                async move {format!("{method} dav")}
+            }),
+        "/files"
+            .GET(|| async {"files"}),
+        "/search"
+            .method("QUERY", |req: &Request| {
+                assert_eq!(req.method, Method::Extension("QUERY"));
+                async {"search"}
+            }),
+        "/dav/:name".By(Ohkami::new((
+            "/lock".method("LOCK", |name: String| async move {format!("lock {name}")}),
+        ))),
+    )).test(); {
+        let req = TestRequest::method("PROPFIND", "/dav");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::OK);
+        assert_eq!(res.text(), Some("PROPFIND dav"));
+    } {
+        let req = TestRequest::GET("/dav");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("get"));
+    } {
+        let req = TestRequest::method("QUERY", "/search");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("search"));
+    } {
+        let req = TestRequest::method("LOCK", "/dav/a.txt/lock");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.text(), Some("lock a.txt"));
+    } {
+        let req = TestRequest::method("PROPFIND", "/files");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::MethodNotAllowed);
+        assert_eq!(res.header("Allow"), Some("GET, HEAD, OPTIONS"));
+    } {
+        let req = TestRequest::PUT("/dav");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::MethodNotAllowed);
+        assert_eq!(res.header("Allow"), Some("GET, PROPFIND, HEAD, OPTIONS"));
+    } {
+        let req = TestRequest::method("PROPFIND", "/unknown");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotFound);
+    } {
+        let req = TestRequest::method("MKCOL", "/dav");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotImplemented);
+    }
+
+    let t = Ohkami::new((
+        "/".GET(|| async {"index"}),
+    )).test(); {
+        /* routed in another `Ohkami`, but not in this one */
+        let req = TestRequest::method("PROPFIND", "/");
+        let res = t.oneshot(req).await;
+        assert_eq!(res.status(), Status::NotImplemented);
+    }
+}
+
+#[test]
+#[should_panic = "Invalid method name: `PROP FIND`"]
+fn invalid_extension_method() {
+    let _ = "/dav".method("PROP FIND", || async {""});
+}
@@ -0,0 +1,980 @@
+#![cfg(all(feature="rt_tokio", feature="DEBUG"))]
+
+use crate::prelude::*;
+use crate::ServerConfig;
+use std::time::Duration;
+use tokio::net::TcpStream;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use super::_test_util::{vacant_port, serve_in_background, connect};
+
+
+fn howl_in_background(config: ServerConfig, ohkami: fn() -> Ohkami) -> u16 {
+    let port = vacant_port();
+    serve_in_background(move || ohkami().howl_with(config, ("127.0.0.1", port)));
+    port
+}
+
+/// Returns the port, the trigger of shutdown, and the notification that `howl_with_shutdown` returned
+fn howl_in_background_with_shutdown(config: ServerConfig, ohkami: fn() -> Ohkami) -> (
+    u16,
+    tokio::sync::oneshot::Sender<()>,
+    std::sync::mpsc::Receiver<()>,
+) {
+    let port = vacant_port();
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel();
+    let (returned_tx, returned_rx) = std::sync::mpsc::channel();
+    serve_in_background(move || async move {
+        ohkami().howl_with_shutdown(config, ("127.0.0.1", port), async {
+            let _ = shutdown_rx.await;
+        }).await;
+        returned_tx.send(()).unwrap();
+    });
+    (port, shutdown_tx, returned_rx)
+}
+
+/// Read one response, assuming it has `Content-Length`
+async fn read_response(conn: &mut TcpStream) -> Option<String> {
+    let mut buf = Vec::new();
+    loop {
+        let text = String::from_utf8_lossy(&buf);
+        if let Some(head_end) = text.find("\r\n\r\n") {
+            let content_length = text[..head_end].lines()
+                .find_map(|line| line.strip_prefix("Content-Length: "))
+                .map(|len| len.parse::<usize>().unwrap())
+                .unwrap_or(0);
+            if buf.len() >= head_end + 4 + content_length {
+                break Some(text.into_owned())
+            }
+        }
+
+        let mut chunk = [0; 1024];
+        match conn.read(&mut chunk).await {
+            Ok(0) | Err(_) => break None,
+            Ok(n) => buf.extend_from_slice(&chunk[..n]),
+        }
+    }
+}
+
+fn hello() -> Ohkami {
+    Ohkami::new((
+        "/".GET(|| async {"Hello, world!"}),
+    ))
+}
+
+
+#[crate::__rt__::test] async fn test_howl_with_max_connections() {
+    let port = howl_in_background(ServerConfig::new().max_connections(1), hello);
+
+    let mut a = connect(port).await;
+    a.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut a).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    /* `a` is still alive, so `b` has to wait */
+    let mut b = connect(port).await;
+    b.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    assert!(crate::util::timeout_in(Duration::from_millis(300), read_response(&mut b)).await.is_none());
+
+    drop(a);
+    let res = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut b)).await
+        .expect("`b` should be served after `a` is closed")
+        .unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_howl_with_max_connections_rejecting() {
+    let config = ServerConfig::new().max_connections(1).reject_over_limit(true);
+    let open_connections = config.open_connections();
+    let port = howl_in_background(config, hello);
+
+    let mut a = connect(port).await;
+    a.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut a).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+    assert_eq!(open_connections.get(), 1);
+
+    /* `a` is still alive, so `b` is rejected at once */
+    let mut b = connect(port).await;
+    let mut res = String::new();
+    crate::util::timeout_in(Duration::from_secs(3), b.read_to_string(&mut res)).await
+        .expect("`b` should be rejected without waiting")
+        .unwrap();
+    assert!(res.starts_with("HTTP/1.1 503 Service Unavailable\r\n"), "{res}");
+    assert!(res.contains("\r\nConnection: close\r\n"), "{res}");
+    assert_eq!(open_connections.get(), 1);
+
+    drop(a);
+    crate::util::timeout_in(Duration::from_secs(3), async {
+        while open_connections.get() > 0 {
+            crate::__rt__::sleep(Duration::from_millis(10)).await
+        }
+    }).await.expect("`a` should be counted out after closed");
+
+    let mut c = connect(port).await;
+    c.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut c).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_rejecting_without_proxy_header() {
+    let config = ServerConfig::new().max_connections(1).reject_over_limit(true).proxy_protocol(true);
+    let port = howl_in_background(config, hello);
+
+    let mut a = connect(port).await;
+    a.write_all(b"PROXY TCP4 192.0.2.1 192.0.2.2 56324 443\r\nGET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut a).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    /* `b` sends nothing, not even a PROXY header, but is rejected at once */
+    let mut b = connect(port).await;
+    let mut res = String::new();
+    crate::util::timeout_in(Duration::from_secs(3), b.read_to_string(&mut res)).await
+        .expect("`b` should be rejected without waiting for a PROXY header")
+        .unwrap();
+    assert!(res.starts_with("HTTP/1.1 503 Service Unavailable\r\n"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_howl_with_keepalive_timeout() {
+    let port = howl_in_background(ServerConfig::new().keepalive_timeout(Duration::from_millis(100)), hello);
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    assert!(read_response(&mut conn).await.is_some());
+
+    let closed = crate::util::timeout_in(Duration::from_secs(3), conn.read(&mut [0; 1])).await;
+    assert!(matches!(closed, Some(Ok(0))), "connection should be closed by server");
+}
+
+#[crate::__rt__::test] async fn test_howl_with_payload_limit() {
+    let port = howl_in_background(ServerConfig::new().payload_limit(4), || Ohkami::new((
+        "/".POST(|| async {"accepted"}),
+    )));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"POST / HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 413 Payload Too Large\r\n"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_keepalive_timeout_restarts_after_each_response() {
+    let port = howl_in_background(ServerConfig::new().keepalive_timeout(Duration::from_millis(300)), hello);
+
+    /* busy for longer than `keepalive_timeout` in total, but never idle that long */
+    let mut conn = connect(port).await;
+    for _ in 0..5 {
+        conn.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let res = read_response(&mut conn).await.expect("connection should be kept alive");
+        assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+        crate::__rt__::sleep(Duration::from_millis(150)).await;
+    }
+}
+
+#[crate::__rt__::test] async fn test_header_read_timeout() {
+    let port = howl_in_background(ServerConfig::new().header_read_timeout(Duration::from_millis(200)), hello);
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET / HTTP/1.1\r\nHost: loc").await.unwrap();
+    let res = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut conn)).await
+        .flatten().expect("should respond 408");
+    assert!(res.starts_with("HTTP/1.1 408 Request Timeout\r\n"), "{res}");
+
+    let closed = crate::util::timeout_in(Duration::from_secs(3), conn.read(&mut [0; 1])).await;
+    assert!(matches!(closed, Some(Ok(0))), "connection should be closed by server");
+}
+
+#[crate::__rt__::test] async fn test_body_read_timeout() {
+    let port = howl_in_background(ServerConfig::new().body_read_timeout(Duration::from_millis(200)), || Ohkami::new((
+        "/".POST(|| async {"accepted"}),
+    )));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nhel").await.unwrap();
+    let res = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut conn)).await
+        .flatten().expect("should respond 408");
+    assert!(res.starts_with("HTTP/1.1 408 Request Timeout\r\n"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_headers_over_multiple_reads() {
+    let port = howl_in_background(ServerConfig::new().max_header_size(1 << 12), || Ohkami::new((
+        "/".GET(|req: &crate::Request| {
+            let len = req.headers.Cookie().map(str::len).unwrap_or(0);
+            async move {format!("{len}")}
+        }),
+    )));
+
+    let cookie = "k=v; ".repeat(500).trim_end().to_string();
+
+    let mut conn = connect(port).await;
+    for part in [
+        "GET / HTTP/1.1\r\n".to_string(),
+        format!("Cookie: {}", &cookie[..1000]),
+        format!("{}\r\n", &cookie[1000..]),
+        "\r\n".to_string(),
+    ] {
+        conn.write_all(part.as_bytes()).await.unwrap();
+        crate::__rt__::sleep(Duration::from_millis(20)).await;
+    }
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with(&format!("\r\n\r\n{}", cookie.len())), "{res}");
+
+    /* over `max_header_size` */
+    conn.write_all(format!("GET / HTTP/1.1\r\nCookie: {cookie}{cookie}\r\n\r\n").as_bytes()).await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 431 Request Header Fields Too Large\r\n"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_chunked_request_then_keepalive() {
+    let port = howl_in_background(ServerConfig::default(), || Ohkami::new((
+        "/echo".POST(|req: &crate::Request| {
+            let body = String::from_utf8(req.payload.as_deref().unwrap_or_default().to_vec()).unwrap();
+            async move {body}
+        }),
+    )));
+
+    let mut conn = connect(port).await;
+    for part in [
+        "POST /echo HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n",
+        "5\r\nHello\r\n",
+        "8\r\n, world!\r\n",
+        "0\r\nX-Checksum: 42\r\n\r\n",
+    ] {
+        conn.write_all(part.as_bytes()).await.unwrap();
+        crate::__rt__::sleep(Duration::from_millis(20)).await;
+    }
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    /* chunk bytes must not leak into the next request */
+    conn.write_all(b"POST /echo HTTP/1.1\r\nContent-Length: 4\r\n\r\nnext").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nnext"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_pipelined_requests() {
+    let port = howl_in_background(ServerConfig::default(), || Ohkami::new((
+        "/:n".GET(|n: usize| async move {format!("{n}")}),
+    )));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"\
+        GET /1 HTTP/1.1\r\n\r\n\
+        GET /2 HTTP/1.1\r\n\r\n\
+        GET /3 HTTP/1.1\r\n\r\n\
+    ").await.unwrap();
+
+    /* responses may arrive in one packet */
+    let mut received = String::new();
+    while received.matches("HTTP/1.1 200 OK").count() < 3 {
+        let mut chunk = [0; 1024];
+        match crate::util::timeout_in(Duration::from_secs(3), conn.read(&mut chunk)).await {
+            Some(Ok(n)) if n > 0 => received.push_str(std::str::from_utf8(&chunk[..n]).unwrap()),
+            _ => panic!("pipelined requests should be all served: {received}"),
+        }
+    }
+    let bodies = received.split("HTTP/1.1 ").skip(1)
+        .map(|res| res.rsplit("\r\n\r\n").next().unwrap())
+        .collect::<Vec<_>>();
+    assert_eq!(bodies, ["1", "2", "3"]);
+}
+
+#[crate::__rt__::test] async fn test_expect_100_continue() {
+    let port = howl_in_background(ServerConfig::new().payload_limit(8), || Ohkami::new((
+        "/upload".POST(|req: &crate::Request| {
+            let len = req.payload.as_deref().map(<[u8]>::len).unwrap_or(0);
+            async move {format!("{len}")}
+        }),
+    )));
+
+    /* interim response before the body */
+    let mut conn = connect(port).await;
+    conn.write_all(b"POST /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 5\r\n\r\n").await.unwrap();
+    let interim = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut conn)).await
+        .flatten().expect("should respond 100 Continue");
+    assert_eq!(interim, "HTTP/1.1 100 Continue\r\n\r\n");
+    conn.write_all(b"hello").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n") && res.ends_with("\r\n\r\n5"), "{res}");
+
+    /* rejected immediately without the body */
+    for (request, expected) in [
+        ("POST /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 9\r\n\r\n",  "HTTP/1.1 413 Payload Too Large\r\n"),
+        ("POST /missing HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 5\r\n\r\n", "HTTP/1.1 417 Expectation Failed\r\n"),
+        ("POST /upload HTTP/1.1\r\nExpect: 200-ok\r\nContent-Length: 5\r\n\r\n",        "HTTP/1.1 417 Expectation Failed\r\n"),
+    ] {
+        let mut conn = connect(port).await;
+        conn.write_all(request.as_bytes()).await.unwrap();
+        let res = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut conn)).await
+            .flatten().expect("should respond without waiting for the body");
+        assert!(res.starts_with(expected), "{res}");
+
+        let closed = crate::util::timeout_in(Duration::from_secs(3), conn.read(&mut [0; 1])).await;
+        assert!(matches!(closed, Some(Ok(0))), "connection should be closed by server");
+    }
+}
+
+#[crate::__rt__::test] async fn test_expect_100_continue_extension_method() {
+    fn len(req: &crate::Request) -> impl std::future::Future<Output = String> {
+        let len = req.payload.as_deref().map(<[u8]>::len).unwrap_or(0);
+        async move {format!("{len}")}
+    }
+    let port = howl_in_background(ServerConfig::default(), || Ohkami::new((
+        "/upload".POST(len),
+        "/search".method("QUERY", len),
+    )));
+
+    /* not checked by the method of the previous request on the connection */
+    let mut conn = connect(port).await;
+    conn.write_all(b"POST /upload HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\n5"), "{res}");
+    conn.write_all(b"QUERY /search HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 5\r\n\r\n").await.unwrap();
+    let interim = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut conn)).await
+        .flatten().expect("should respond 100 Continue");
+    assert_eq!(interim, "HTTP/1.1 100 Continue\r\n\r\n");
+    conn.write_all(b"hello").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n") && res.ends_with("\r\n\r\n5"), "{res}");
+
+    for (request, expected) in [
+        ("QUERY /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 5\r\n\r\n", "HTTP/1.1 417 Expectation Failed\r\n"),
+        ("MKCOL /upload HTTP/1.1\r\nExpect: 100-continue\r\nContent-Length: 5\r\n\r\n", "HTTP/1.1 501 Not Implemented\r\n"),
+    ] {
+        let mut conn = connect(port).await;
+        conn.write_all(b"POST /upload HTTP/1.1\r\nContent-Length: 5\r\n\r\nhello").await.unwrap();
+        read_response(&mut conn).await.unwrap();
+        conn.write_all(request.as_bytes()).await.unwrap();
+        let res = crate::util::timeout_in(Duration::from_secs(3), read_response(&mut conn)).await
+            .flatten().expect("should respond without waiting for the body");
+        assert!(res.starts_with(expected), "{res}");
+    }
+}
+
+#[crate::__rt__::test] async fn test_http10_connection() {
+    let port = howl_in_background(ServerConfig::default(), hello);
+
+    /* closed after response by default */
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET / HTTP/1.0\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.contains("\r\nConnection: close\r\n") && res.ends_with("\r\n\r\nHello, world!"), "{res}");
+    let closed = crate::util::timeout_in(Duration::from_secs(3), conn.read(&mut [0; 1])).await;
+    assert!(matches!(closed, Some(Ok(0))), "connection should be closed by server");
+
+    /* kept alive on request */
+    let mut conn = connect(port).await;
+    for _ in 0..2 {
+        conn.write_all(b"GET / HTTP/1.0\r\nConnection: Keep-Alive\r\n\r\n").await.unwrap();
+        let res = read_response(&mut conn).await.expect("connection should be kept alive");
+        assert!(res.contains("\r\nConnection: keep-alive\r\n") && res.ends_with("\r\n\r\nHello, world!"), "{res}");
+    }
+}
+
+#[crate::__rt__::test] async fn test_unknown_method_not_implemented() {
+    let port = howl_in_background(ServerConfig::default(), hello);
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"BREW / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.expect("should be responded, not dropped");
+    assert!(res.starts_with("HTTP/1.1 501 Not Implemented\r\n"), "{res}");
+}
+
+#[cfg(feature="sse")]
+#[crate::__rt__::test] async fn test_http10_stream_not_chunked() {
+    let port = howl_in_background(ServerConfig::new().cancel_on_disconnect(true), || Ohkami::new((
+        "/sse".GET(|| async {
+            Response::OK().with_stream(Box::pin(ohkami_lib::stream::queue(|mut q| async move {
+                for i in 1..=3 {
+                    q.push(Ok::<_, std::convert::Infallible>(format!("{i}")));
+                }
+            })))
+        }),
+    )));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /sse HTTP/1.0\r\nConnection: keep-alive\r\n\r\n").await.unwrap();
+    let mut res = String::new();
+    crate::util::timeout_in(Duration::from_secs(3), conn.read_to_string(&mut res)).await
+        .expect("stream should be delimited by closing connection")
+        .unwrap();
+    assert!(!res.contains("Transfer-Encoding"), "{res}");
+    assert!(res.contains("\r\nConnection: close\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\ndata: 1\n\ndata: 2\n\ndata: 3\n\n"), "{res}");
+}
+
+#[cfg(feature="http2")]
+async fn h2_client(port: u16) -> h2::client::SendRequest<bytes::Bytes> {
+    let (client, connection) = h2::client::handshake(connect(port).await).await
+        .expect("HTTP/2 handshake failed");
+    tokio::spawn(connection);
+    client
+}
+
+#[cfg(feature="http2")]
+async fn h2_request(
+    client:  &mut h2::client::SendRequest<bytes::Bytes>,
+    request: http::Request<()>,
+    body:    &'static [u8],
+) -> (http::response::Parts, String) {
+    let (response, mut send) = client.send_request(request, body.is_empty()).unwrap();
+    if !body.is_empty() {
+        send.send_data(bytes::Bytes::from_static(body), true).unwrap();
+    }
+
+    let (head, mut recv) = response.await.unwrap().into_parts();
+    let mut content = Vec::new();
+    while let Some(data) = recv.data().await {
+        let data = data.unwrap();
+        recv.flow_control().release_capacity(data.len()).unwrap();
+        content.extend_from_slice(&data);
+    }
+    (head, String::from_utf8(content).unwrap())
+}
+
+#[cfg(feature="http2")]
+#[crate::__rt__::test] async fn test_http2_prior_knowledge() {
+    let port = howl_in_background(ServerConfig::new(), || Ohkami::new((
+        "/".GET(|| async {"Hello, world!"}),
+        "/echo".POST(|crate::format::Text(body): crate::format::Text<String>| async move {body}),
+        "/cookie".GET(|req: &Request| {
+            let cookie = req.headers.Cookie().unwrap_or_default().to_string();
+            async move {cookie}
+        }),
+    )));
+
+    let mut client = h2_client(port).await;
+
+    let (head, content) = h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/"))
+        .body(()).unwrap(), b"").await;
+    assert_eq!(head.status, 200);
+    assert_eq!(head.headers["content-length"], "13");
+    assert!(head.headers.get("connection").is_none());
+    assert_eq!(content, "Hello, world!");
+
+    let (head, content) = h2_request(&mut client, http::Request::post(format!("http://127.0.0.1:{port}/echo"))
+        .header("content-type", "text/plain")
+        .body(()).unwrap(), b"Hello, HTTP/2!").await;
+    assert_eq!(head.status, 200);
+    assert_eq!(content, "Hello, HTTP/2!");
+
+    /* split `cookie` fields are joined into one ( RFC 9113, 8.2.3 ) */
+    let (head, content) = h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/cookie"))
+        .header("cookie", "a=1")
+        .header("cookie", "b=2")
+        .body(()).unwrap(), b"").await;
+    assert_eq!(head.status, 200);
+    assert_eq!(content, "a=1; b=2");
+
+    let (head, _) = h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/not-found"))
+        .body(()).unwrap(), b"").await;
+    assert_eq!(head.status, 404);
+
+    /* HTTP/1.1 is still served on the same port */
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+}
+
+#[cfg(feature="http2")]
+#[crate::__rt__::test] async fn test_http2_multiplexed_streams() {
+    let port = howl_in_background(ServerConfig::new(), || Ohkami::new((
+        "/slow".GET(|| async {
+            crate::__rt__::sleep(Duration::from_secs(1)).await;
+            "slow"
+        }),
+        "/fast".GET(|| async {"fast"}),
+    )));
+
+    let client = h2_client(port).await;
+
+    let slow = tokio::spawn({
+        let mut client = client.clone();
+        async move {
+            h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/slow"))
+                .body(()).unwrap(), b"").await
+        }
+    });
+
+    /* served while `/slow` is in flight on the same connection */
+    let (head, content) = crate::util::timeout_in(Duration::from_millis(500), {
+        let mut client = client.clone();
+        async move {
+            h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/fast"))
+                .body(()).unwrap(), b"").await
+        }
+    }).await.expect("`/fast` should not wait for `/slow`");
+    assert_eq!(head.status, 200);
+    assert_eq!(content, "fast");
+
+    let (head, content) = slow.await.unwrap();
+    assert_eq!(head.status, 200);
+    assert_eq!(content, "slow");
+}
+
+fn slow() -> Ohkami {
+    Ohkami::new((
+        "/".GET(|| async {"Hello, world!"}),
+        "/slow".GET(|| async {
+            crate::__rt__::sleep(Duration::from_millis(500)).await;
+            "slow"
+        }),
+    ))
+}
+
+#[crate::__rt__::test] async fn test_howl_with_shutdown_drains_sessions() {
+    let (port, shutdown, returned) = howl_in_background_with_shutdown(ServerConfig::new(), slow);
+
+    let mut idle = connect(port).await;
+    idle.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut idle).await.unwrap();
+    assert!(!res.contains("Connection: close"), "{res}");
+
+    let mut busy = connect(port).await;
+    busy.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+
+    shutdown.send(()).unwrap();
+
+    /* idle keep-alive connection is closed without waiting for `keepalive_timeout` */
+    let closed = crate::util::timeout_in(Duration::from_millis(300), idle.read(&mut [0; 1])).await;
+    assert!(matches!(closed, Some(Ok(0))), "idle connection should be closed by server");
+
+    /* in-flight request is finished, and told that the connection is closing */
+    let res = read_response(&mut busy).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n"), "{res}");
+    assert!(res.contains("\r\nConnection: close\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\nslow"), "{res}");
+    assert!(matches!(busy.read(&mut [0; 1]).await, Ok(0)));
+
+    returned.recv_timeout(Duration::from_secs(3)).expect("`howl_with_shutdown` should return after drain");
+
+    assert!(TcpStream::connect(("127.0.0.1", port)).await.is_err(), "listener should be closed");
+}
+
+#[cfg(feature="http2")]
+#[crate::__rt__::test] async fn test_http2_streams_bounded_and_drained() {
+    let (port, shutdown, returned) = howl_in_background_with_shutdown(ServerConfig::new().max_concurrent_streams(1), slow);
+
+    let mut client = h2_client(port).await;
+    let (head, _) = h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/"))
+        .body(()).unwrap(), b"").await;
+    assert_eq!(head.status, 200);
+    assert_eq!(client.current_max_send_streams(), 1);
+
+    let slow = tokio::spawn({
+        let mut client = client.clone();
+        async move {
+            h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/slow"))
+                .body(()).unwrap(), b"").await
+        }
+    });
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+
+    /* the in-flight stream is drained as a part of its connection */
+    shutdown.send(()).unwrap();
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+    assert!(returned.try_recv().is_err(), "`howl_with_shutdown` should wait for the in-flight stream");
+
+    let (head, content) = slow.await.unwrap();
+    assert_eq!(head.status, 200);
+    assert_eq!(content, "slow");
+    returned.recv_timeout(Duration::from_secs(3)).expect("`howl_with_shutdown` should return after the stream");
+}
+
+#[cfg(feature="http2")]
+#[crate::__rt__::test] async fn test_http2_response_write_timeout() {
+    let port = howl_in_background(ServerConfig::new().response_write_timeout(Duration::from_millis(200)), || Ohkami::new((
+        "/".GET(|| async {"Hello, world!"}),
+        "/large".GET(|| async {"a".repeat(1 << 20)}),
+    )));
+
+    let mut client = h2_client(port).await;
+    let (response, _) = client.send_request(http::Request::get(format!("http://127.0.0.1:{port}/large"))
+        .body(()).unwrap(), true).unwrap();
+    let mut recv = response.await.unwrap().into_body();
+
+    /* not reading beyond the initial window, so the stream is reset */
+    crate::__rt__::sleep(Duration::from_millis(500)).await;
+    let mut received = 0;
+    let reset = crate::util::timeout_in(Duration::from_secs(3), async {
+        loop {
+            match recv.data().await {
+                Some(Ok(data)) => received += data.len(),
+                Some(Err(e))   => break e,
+                None           => panic!("stream should be reset, not completed"),
+            }
+        }
+    }).await.expect("stream should be reset after `response_write_timeout`");
+    assert_eq!(reset.reason(), Some(h2::Reason::CANCEL));
+    assert!(received < 1 << 20);
+    drop(recv); /* giving back the connection's window */
+
+    /* other streams on the connection are still served */
+    let (head, content) = h2_request(&mut client, http::Request::get(format!("http://127.0.0.1:{port}/"))
+        .body(()).unwrap(), b"").await;
+    assert_eq!(head.status, 200);
+    assert_eq!(content, "Hello, world!");
+}
+
+#[crate::__rt__::test] async fn test_shutdown_timeout_closes_lingering_sessions() {
+    let (port, shutdown, returned) = howl_in_background_with_shutdown(
+        ServerConfig::new().shutdown_timeout(Duration::from_millis(100)),
+        || Ohkami::new((
+            "/hang".GET(|| async {
+                crate::__rt__::sleep(Duration::from_secs(60)).await;
+                "done"
+            }),
+        ))
+    );
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /hang HTTP/1.1\r\n\r\n").await.unwrap();
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+
+    shutdown.send(()).unwrap();
+
+    returned.recv_timeout(Duration::from_secs(3)).expect("`howl_with_shutdown` should return after `shutdown_timeout`");
+    let closed = crate::util::timeout_in(Duration::from_secs(1), conn.read(&mut [0; 1])).await;
+    assert!(matches!(closed, Some(Ok(0) | Err(_))), "lingering connection should be closed by server");
+}
+
+#[cfg(unix)]
+#[crate::__rt__::test] async fn test_howl_unix() {
+    let path = std::env::temp_dir().join(format!("ohkami-test-{}.sock", std::process::id()));
+
+    /* stale socket file left by a previous process */
+    drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(path.exists());
+
+    serve_in_background({
+        let path = path.clone();
+        move || Ohkami::new((
+            "/peer".GET(|req: &Request| {
+                let (peer, ip) = (req.peer.clone(), req.ip);
+                async move {format!("{peer} {ip} {:?}", peer.ip())}
+            }),
+        )).howl_unix(path)
+    });
+
+    let mut conn = loop {
+        match tokio::net::UnixStream::connect(&path).await {
+            Ok(conn) => break conn,
+            Err(_)   => crate::__rt__::sleep(Duration::from_millis(10)).await,
+        }
+    };
+    conn.write_all(b"GET /peer HTTP/1.1\r\nConnection: close\r\n\r\n").await.unwrap();
+
+    let mut res = String::new();
+    conn.read_to_string(&mut res).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\nunix:(unnamed) 0.0.0.0 None"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_howl_on_prebound_listeners() {
+    use crate::Listener;
+
+    let tcp = Listener::bind("127.0.0.1:0").unwrap();
+    let port = tcp.local_addr().unwrap().port();
+
+    let path = std::env::temp_dir().join(format!("ohkami-test-on-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let unix = Listener::from(std::os::unix::net::UnixListener::bind(&path).unwrap());
+    assert!(unix.local_addr().is_err());
+
+    serve_in_background(move || hello().howl_on(ServerConfig::new(), [tcp, unix]));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    let mut conn = tokio::net::UnixStream::connect(&path).await.unwrap();
+    conn.write_all(b"GET / HTTP/1.1\r\nConnection: close\r\n\r\n").await.unwrap();
+    let mut res = String::new();
+    conn.read_to_string(&mut res).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    /* not bound by Ohkami, so left to the owner */
+    assert!(path.exists());
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[cfg(unix)]
+#[test] fn test_listening_socket_family() {
+    use super::listener::listening_socket_family;
+    use std::os::fd::AsRawFd;
+
+    let tcp = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+    assert_eq!(listening_socket_family(tcp.as_raw_fd()).unwrap(), libc::AF_INET);
+
+    let path = std::env::temp_dir().join(format!("ohkami-test-family-{}.sock", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    let unix = std::os::unix::net::UnixListener::bind(&path).unwrap();
+    assert_eq!(listening_socket_family(unix.as_raw_fd()).unwrap(), libc::AF_UNIX);
+    std::fs::remove_file(&path).unwrap();
+
+    /* connected, not listening */
+    let stream = std::net::TcpStream::connect(tcp.local_addr().unwrap()).unwrap();
+    assert!(listening_socket_family(stream.as_raw_fd()).is_err());
+
+    /* not a stream socket */
+    let udp = std::net::UdpSocket::bind("127.0.0.1:0").unwrap();
+    assert!(listening_socket_family(udp.as_raw_fd()).is_err());
+
+    /* not a socket */
+    let file = std::fs::File::open(std::env::current_exe().unwrap()).unwrap();
+    assert!(listening_socket_family(file.as_raw_fd()).is_err());
+}
+
+#[crate::__rt__::test] async fn test_accept_any_takes_turns() {
+    use super::listener::RtListener;
+    use crate::request::PeerAddr;
+
+    let (a, b) = (crate::Listener::bind("127.0.0.1:0").unwrap(), crate::Listener::bind("127.0.0.1:0").unwrap());
+    let (port_a, port_b) = (a.local_addr().unwrap().port(), b.local_addr().unwrap().port());
+    let listeners = [RtListener::from_listener(a).unwrap(), RtListener::from_listener(b).unwrap()];
+
+    /* both have connections waiting, and the first has more */
+    let mut clients_a = Vec::new();
+    for _ in 0..3 {clients_a.push(connect(port_a).await)}
+    let client_b = connect(port_b).await;
+    let port_of_b = client_b.local_addr().unwrap().port();
+
+    let next = std::sync::atomic::AtomicUsize::new(0);
+    let mut accepted_from_b = false;
+    for _ in 0..2 {
+        let (_, peer) = RtListener::accept_any(&listeners, &next).await.unwrap();
+        let PeerAddr::Tcp(peer) = peer else {panic!("unexpected peer: {peer}")};
+        accepted_from_b |= peer.port() == port_of_b;
+    }
+    assert!(accepted_from_b, "the second listener should be taken within two accepts");
+}
+
+#[crate::__rt__::test] async fn test_howl_with_proxy_protocol() {
+    let port = howl_in_background(ServerConfig::new().proxy_protocol(true), || Ohkami::new((
+        "/client".GET(|req: &Request| {
+            let (ip, peer) = (req.ip, req.peer.clone());
+            async move {format!("{ip} {peer}")}
+        }),
+        "/proxy".GET(|proxy: &crate::ProxyInfo| {
+            let (destination, id) = (proxy.destination(), proxy.tlv(0x05).map(<[u8]>::to_vec));
+            async move {format!("{destination:?} {:?}", id.map(String::from_utf8))}
+        }),
+    )));
+
+    /* v1 */
+    let mut conn = connect(port).await;
+    conn.write_all(b"PROXY TCP4 192.0.2.1 198.51.100.1 56324 443\r\nGET /client HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\n192.0.2.1 192.0.2.1:56324"), "{res}");
+
+    /* the header is only at the start of the connection */
+    conn.write_all(b"GET /client HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\n192.0.2.1 192.0.2.1:56324"), "{res}");
+
+    /* v2, TCP over IPv6, with a TLV of unique ID */
+    let mut header = b"\r\n\r\n\0\r\nQUIT\n\x21\x21".to_vec();
+    let mut payload = Vec::new();
+    payload.extend_from_slice(&"2001:db8::1".parse::<std::net::Ipv6Addr>().unwrap().octets());
+    payload.extend_from_slice(&"2001:db8::2".parse::<std::net::Ipv6Addr>().unwrap().octets());
+    payload.extend_from_slice(&56324_u16.to_be_bytes());
+    payload.extend_from_slice(&443_u16.to_be_bytes());
+    payload.extend_from_slice(b"\x05\x00\x03abc");
+    header.extend_from_slice(&(payload.len() as u16).to_be_bytes());
+    header.extend_from_slice(&payload);
+
+    let mut conn = connect(port).await;
+    conn.write_all(&[&header[..], b"GET /client HTTP/1.1\r\n\r\nGET /proxy HTTP/1.1\r\n\r\n"].concat()).await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\n2001:db8::1 [2001:db8::1]:56324"), "{res}");
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nSome([2001:db8::2]:443) Some(Ok(\"abc\"))"), "{res}");
+
+    /* v2 LOCAL, from the proxy itself */
+    let mut conn = connect(port).await;
+    conn.write_all(b"\r\n\r\n\0\r\nQUIT\n\x20\x00\x00\x00GET /client HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.contains("\r\n\r\n127.0.0.1 127.0.0.1:"), "{res}");
+
+    /* without the header */
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /client HTTP/1.1\r\n\r\n").await.unwrap();
+    assert_eq!(read_response(&mut conn).await, None);
+}
+
+#[crate::__rt__::test] async fn test_panic_after_await_in_handler() {
+    fn panicking() -> Ohkami {
+        Ohkami::new((
+            "/panic/:id".GET(|id: usize| async move {
+                crate::__rt__::sleep(Duration::from_millis(10)).await;
+                if id > 0 {panic!("Panicked at #{id}")}
+                "never"
+            }),
+            "/".GET(|| async {"Hello, world!"}),
+        ))
+    }
+
+    let port = howl_in_background(ServerConfig::new(), panicking);
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /panic/1 HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 500 Internal Server Error\r\n"), "{res}");
+
+    /* the connection survives the panic */
+    conn.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    let port = howl_in_background(ServerConfig::new(), || panicking().on_panic(|panic| {
+        Response::ServiceUnavailable().with_text(format!("{} {} {:?}",
+            panic.method(), panic.path(), panic.message()
+        ))
+    }));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /panic/42 HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.starts_with("HTTP/1.1 503 Service Unavailable\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\nGET /panic/42 Some(\"Panicked at #42\")"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_disconnection_closes_quietly() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static REPORTED: AtomicUsize = AtomicUsize::new(0);
+
+    let port = howl_in_background(
+        ServerConfig::new().on_io_error(|_, _| {REPORTED.fetch_add(1, Ordering::SeqCst);}),
+        || Ohkami::new((
+            "/".GET(|| async {"Hello, world!"}),
+            "/large".GET(|| async {"x".repeat(1 << 24)}),
+        )),
+    );
+
+    /* mid-body */
+    let mut conn = connect(port).await;
+    conn.write_all(b"POST / HTTP/1.1\r\nContent-Length: 100\r\n\r\nabc").await.unwrap();
+    drop(conn);
+
+    /* mid-response */
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /large HTTP/1.1\r\n\r\n").await.unwrap();
+    conn.read_exact(&mut [0; 1024]).await.unwrap();
+    drop(conn);
+
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+    let res = read_response(&mut conn).await.unwrap();
+    assert!(res.ends_with("\r\n\r\nHello, world!"), "{res}");
+
+    assert_eq!(REPORTED.load(Ordering::SeqCst), 0);
+}
+
+#[crate::__rt__::test] async fn test_disconnect_cancels_handler() {
+    use crate::Disconnected;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static NOTIFIED: AtomicUsize = AtomicUsize::new(0);
+    static FINISHED: AtomicUsize = AtomicUsize::new(0);
+
+    fn watching() -> Ohkami {
+        Ohkami::new((
+            "/slow".GET(|disconnected: Disconnected| async move {
+                crate::__rt__::spawn(async move {
+                    disconnected.await;
+                    NOTIFIED.fetch_add(1, Ordering::SeqCst);
+                });
+                crate::__rt__::sleep(Duration::from_millis(300)).await;
+                FINISHED.fetch_add(1, Ordering::SeqCst);
+                "done"
+            }),
+        ))
+    }
+
+    /* closed by FIN, or reset by RST with `abort` */
+    async fn request_and_close(port: u16, abort: bool) {
+        let mut conn = connect(port).await;
+        conn.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+        crate::__rt__::sleep(Duration::from_millis(100)).await;
+        if abort {conn.set_zero_linger().unwrap()}
+        drop(conn);
+        crate::__rt__::sleep(Duration::from_millis(500)).await;
+    }
+
+    let port = howl_in_background(ServerConfig::new().cancel_on_disconnect(true), watching);
+    request_and_close(port, false).await;
+    assert_eq!(NOTIFIED.load(Ordering::SeqCst), 1);
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 0);
+    request_and_close(port, true).await;
+    assert_eq!(NOTIFIED.load(Ordering::SeqCst), 2);
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 0);
+
+    let port = howl_in_background(ServerConfig::default(), watching);
+    request_and_close(port, false).await;
+    assert_eq!(NOTIFIED.load(Ordering::SeqCst), 3);
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 1);
+
+    /* half-closing after the request is notified, but still responded without `cancel_on_disconnect` */
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+    conn.shutdown().await.unwrap();
+    let mut res = String::new();
+    conn.read_to_string(&mut res).await.unwrap();
+    assert!(res.ends_with("\r\n\r\ndone"), "{res}");
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 2);
+    assert_eq!(NOTIFIED.load(Ordering::SeqCst), 4);
+
+    /* the next request sent while handling is not taken as disconnection */
+    let port = howl_in_background(ServerConfig::new().cancel_on_disconnect(true), watching);
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+    conn.write_all(b"GET /slow HTTP/1.1\r\n\r\n").await.unwrap();
+    for _ in 0..2 {
+        let res = read_response(&mut conn).await.unwrap();
+        assert!(res.ends_with("\r\n\r\ndone"), "{res}");
+    }
+    assert_eq!(FINISHED.load(Ordering::SeqCst), 4);
+    assert_eq!(NOTIFIED.load(Ordering::SeqCst), 4);
+}
+
+#[cfg(feature="sse")]
+#[crate::__rt__::test] async fn test_disconnect_stops_stream() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    static PUSHED: AtomicUsize = AtomicUsize::new(0);
+
+    let port = howl_in_background(ServerConfig::new().cancel_on_disconnect(true), || Ohkami::new((
+        "/sse".GET(|| async {
+            Response::OK().with_stream(Box::pin(ohkami_lib::stream::queue(|mut q| async move {
+                loop {
+                    crate::__rt__::sleep(Duration::from_millis(100)).await;
+                    PUSHED.fetch_add(1, Ordering::SeqCst);
+                    q.push(Ok::<_, std::convert::Infallible>("tick"));
+                }
+            })))
+        }),
+    )));
+
+    let mut conn = connect(port).await;
+    conn.write_all(b"GET /sse HTTP/1.1\r\n\r\n").await.unwrap();
+    let mut head = Vec::new();
+    while !head.windows(4).any(|w| w == b"\r\n\r\n") {
+        let mut buf = [0; 1024];
+        let n = conn.read(&mut buf).await.unwrap();
+        assert_ne!(n, 0, "closed before the response head");
+        head.extend_from_slice(&buf[..n]);
+    }
+    assert!(head.starts_with(b"HTTP/1.1 200 OK\r\n"), "{}", head.escape_ascii());
+    drop(conn);
+
+    crate::__rt__::sleep(Duration::from_millis(50)).await;
+    let pushed = PUSHED.load(Ordering::SeqCst);
+    crate::__rt__::sleep(Duration::from_millis(400)).await;
+    assert_eq!(PUSHED.load(Ordering::SeqCst), pushed);
+}
@@ -0,0 +1,38 @@
+#![cfg(all(feature="rt_tokio", feature="DEBUG"))]
+
+//! Helpers for the tests serving an `Ohkami` on a real socket
+
+use std::{future::Future, time::Duration};
+use tokio::net::TcpStream;
+
+
+pub(crate) fn vacant_port() -> u16 {
+    std::net::TcpListener::bind("127.0.0.1:0").unwrap()
+        .local_addr().unwrap()
+        .port()
+}
+
+/// `Ohkami` is not `Send`, so build and serve it in its own thread
+pub(crate) fn serve_in_background<Serve: Future>(
+    serve: impl FnOnce() -> Serve + Send + 'static
+) -> std::thread::JoinHandle<Serve::Output>
+where
+    Serve::Output: Send + 'static,
+{
+    std::thread::spawn(move || {
+        tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build().unwrap()
+            .block_on(serve())
+    })
+}
+
+/// Connect to the port, waiting for the server to start listening
+pub(crate) async fn connect(port: u16) -> TcpStream {
+    loop {
+        match TcpStream::connect(("127.0.0.1", port)).await {
+            Ok(conn) => break conn,
+            Err(_)   => crate::__rt__::sleep(Duration::from_millis(10)).await,
+        }
+    }
+}
@@ -3,7 +3,7 @@
 use super::router::{TrieRouter, RouteSections};
 use crate::fang::{Handler, IntoHandler};
 use crate::response::Content;
-use crate::Ohkami;
+use crate::{Ohkami, Method};
 
 
 macro_rules! Handlers {
@@ -13,6 +13,8 @@ macro_rules! Handlers {
             $(
                 pub(crate) $method: Option<Handler>,
             )*
+            /// handlers of extension methods
+            pub(crate) extensions: Vec<(&'static str, Handler)>,
         }
         
         impl Handlers {
@@ -22,6 +24,7 @@ macro_rules! Handlers {
                     $(
                         $method: None,
                     )*
+                    extensions: Vec::new(),
                 }
             }
         }
@@ -33,6 +36,24 @@ macro_rules! Handlers {
                     self
                 }
             )*
+
+            /// Set `handler` for the method named `method`, which can be
+            /// an extension one like `PROPFIND` or `QUERY`
+            pub fn method<T>(mut self, method: &'static str, handler: impl IntoHandler<T>) -> Self {
+                let handler = handler.into_handler();
+                match Method::named(method) {
+                    $(
+                        Ok(Method::$method) => {self.$method.replace(handler);}
+                    )*
+                    Ok(Method::Extension(name)) => match self.extensions.iter_mut().find(|(n, _)| *n == name) {
+                        Some((_, h)) => *h = handler,
+                        None         => self.extensions.push((name, handler)),
+                    }
+                    Ok(other) => panic!("`{other}` is handled automatically and can't have a handler"),
+                    Err(e) => panic!("{e}"),
+                }
+                self
+            }
         }
     };
 } Handlers! { GET, PUT, POST, PATCH, DELETE }
@@ -168,6 +189,10 @@ macro_rules! Route {
                 fn $method<T>(self, handler: impl IntoHandler<T>) -> Handlers;
             )*
 
+            /// Route the method named `method`, which can be
+            /// an extension one like `PROPFIND` or `QUERY`
+            fn method<T>(self, method: &'static str, handler: impl IntoHandler<T>) -> Handlers;
+
             fn By(self, another: Ohkami) -> ByAnother;
 
             fn Dir(self, static_files_dir_path: &'static str) -> Dir;
@@ -182,6 +207,10 @@ macro_rules! Route {
                 }
             )*
 
+            fn method<T>(self, method: &'static str, handler: impl IntoHandler<T>) -> Handlers {
+                Handlers::new(self).method(method, handler)
+            }
+
             fn By(self, another: Ohkami) -> ByAnother {
                 ByAnother {
                     route:  RouteSections::from_literal(self),
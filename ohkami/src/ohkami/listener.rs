@@ -0,0 +1,286 @@
+#![cfg(feature="__rt_native__")]
+
+use crate::__rt__;
+use crate::request::PeerAddr;
+use crate::session::Connection;
+use std::io;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+
+/// # Listening socket for `Ohkami::howl_on`
+///
+/// Bound in advance, handed over from another process,
+/// or inherited by systemd socket activation.
+///
+/// This is independent of async runtime, and converted to
+/// the runtime's one when serving starts.
+///
+/// <br>
+///
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::{Listener, ServerConfig};
+///
+/// #[tokio::main]
+/// async fn main() -> std::io::Result<()> {
+///     let listener = Listener::bind("127.0.0.1:0")?;
+///     println!("listening on {}", listener.local_addr()?);
+///
+///     Ohkami::new((
+///         "/".GET(|| async {"Hello, world!"}),
+///     )).howl_on(ServerConfig::new(), [listener]).await;
+///
+///     Ok(())
+/// }
+/// ```
+pub struct Listener(Socket);
+
+enum Socket {
+    Tcp(std::net::TcpListener),
+    #[cfg(unix)]
+    Unix(std::os::unix::net::UnixListener),
+}
+
+impl From<std::net::TcpListener> for Listener {
+    fn from(tcp: std::net::TcpListener) -> Self {
+        Self(Socket::Tcp(tcp))
+    }
+}
+#[cfg(unix)]
+impl From<std::os::unix::net::UnixListener> for Listener {
+    fn from(unix: std::os::unix::net::UnixListener) -> Self {
+        Self(Socket::Unix(unix))
+    }
+}
+
+impl Listener {
+    /// Bind a TCP socket at `address`, like `std::net::TcpListener::bind`
+    pub fn bind(address: impl std::net::ToSocketAddrs) -> io::Result<Self> {
+        std::net::TcpListener::bind(address).map(Self::from)
+    }
+
+    /// Local address of a TCP socket, useful after binding to port `0`.
+    /// This is an error of `Unsupported` for a Unix domain socket.
+    pub fn local_addr(&self) -> io::Result<std::net::SocketAddr> {
+        match &self.0 {
+            Socket::Tcp(tcp) => tcp.local_addr(),
+            #[cfg(unix)]
+            Socket::Unix(_)  => Err(io::Error::new(io::ErrorKind::Unsupported, "Unix domain socket has no `SocketAddr`")),
+        }
+    }
+
+    #[cfg(unix)]
+    /// Listeners passed by systemd socket activation ( `LISTEN_FDS` ),
+    /// empty when this process isn't activated so.
+    /// 
+    /// Every fd is checked to be a listening stream socket before any of them
+    /// is owned, so on error no fd is closed. The fds are owned by the first call,
+    /// and later calls return empty.
+    /// 
+    /// Unlike `sd_listen_fds(1)`, this doesn't unset the environment variables
+    /// since it's not thread-safe to do so after the async runtime has started.
+    /// Child processes don't take the fds anyway, as `LISTEN_PID` isn't theirs.
+    pub fn from_systemd() -> io::Result<Vec<Self>> {
+        use std::os::fd::{FromRawFd, RawFd};
+        use std::sync::atomic::AtomicBool;
+
+        const SD_LISTEN_FDS_START: RawFd = 3;
+
+        static CLAIMED: AtomicBool = AtomicBool::new(false);
+
+        let for_this_process = std::env::var("LISTEN_PID").ok()
+            .and_then(|pid| pid.parse::<u32>().ok())
+            .is_some_and(|pid| pid == std::process::id());
+        if !for_this_process || CLAIMED.swap(true, Ordering::SeqCst) {
+            return Ok(Vec::new())
+        }
+
+        let end = std::env::var("LISTEN_FDS").ok()
+            .and_then(|n| n.parse::<RawFd>().ok())
+            .filter(|n| *n >= 0)
+            .and_then(|n| SD_LISTEN_FDS_START.checked_add(n))
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidInput, "Invalid `LISTEN_FDS`"))?;
+
+        let sockets = (SD_LISTEN_FDS_START..end)
+            .map(|fd| listening_socket_family(fd).map(|family| (fd, family)))
+            .collect::<io::Result<Vec<_>>>()?;
+
+        Ok(sockets.into_iter().map(|(fd, family)| match family {
+            // SAFETY: fds from `SD_LISTEN_FDS_START` are passed to this process by systemd,
+            // checked to be listening sockets, and `CLAIMED` makes them owned only by this call
+            libc::AF_UNIX => Self::from(unsafe {std::os::unix::net::UnixListener::from_raw_fd(fd)}),
+            _             => Self::from(unsafe {std::net::TcpListener::from_raw_fd(fd)}),
+        }).collect())
+    }
+}
+
+#[cfg(unix)]
+/// Address family of `fd` ( `AF_INET`, `AF_INET6` or `AF_UNIX` ),
+/// or an error if it's not such a listening stream socket
+pub(crate) fn listening_socket_family(fd: std::os::fd::RawFd) -> io::Result<libc::c_int> {
+    use std::mem::{size_of, zeroed};
+
+    let invalid = |what: &str| io::Error::new(io::ErrorKind::InvalidInput, format!("fd {fd} is {what}"));
+
+    let sockopt = |name: libc::c_int| {
+        let (mut value, mut len) = (0 as libc::c_int, size_of::<libc::c_int>() as libc::socklen_t);
+        // SAFETY: `value` and `len` are valid for writes of a `c_int` and a `socklen_t`
+        match unsafe {libc::getsockopt(fd, libc::SOL_SOCKET, name, (&mut value as *mut libc::c_int).cast(), &mut len)} {
+            0 => Ok(value),
+            _ => Err(io::Error::last_os_error()),
+        }
+    };
+    if sockopt(libc::SO_TYPE).map_err(|_| invalid("not a socket"))? != libc::SOCK_STREAM {
+        return Err(invalid("not a stream socket"))
+    }
+    if sockopt(libc::SO_ACCEPTCONN)? == 0 {
+        return Err(invalid("not a listening socket"))
+    }
+
+    // SAFETY: all-zero is a valid `sockaddr_storage`
+    let mut addr: libc::sockaddr_storage = unsafe {zeroed()};
+    let mut len = size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+    // SAFETY: `addr` and `len` are valid for writes of a `sockaddr_storage` and a `socklen_t`
+    if unsafe {libc::getsockname(fd, (&mut addr as *mut libc::sockaddr_storage).cast(), &mut len)} != 0 {
+        return Err(io::Error::last_os_error())
+    }
+    match addr.ss_family as libc::c_int {
+        family @ (libc::AF_INET | libc::AF_INET6 | libc::AF_UNIX) => Ok(family),
+        _ => Err(invalid("neither an IP nor a Unix domain socket")),
+    }
+}
+
+/// `Listener` converted to the async runtime's one
+pub(crate) enum RtListener {
+    Tcp(__rt__::TcpListener),
+
+    /// with the path to remove the socket file on drop, if bound by Ohkami
+    #[cfg(unix)]
+    Unix(__rt__::UnixListener, Option<std::path::PathBuf>),
+}
+
+impl RtListener {
+    pub(crate) fn from_listener(Listener(socket): Listener) -> io::Result<Self> {
+        match socket {
+            Socket::Tcp(tcp) => {
+                tcp.set_nonblocking(true)?;
+
+                #[cfg(feature="rt_tokio")]
+                let tcp = __rt__::TcpListener::from_std(tcp)?;
+                #[cfg(feature="rt_async-std")]
+                let tcp = __rt__::TcpListener::from(tcp);
+                #[cfg(feature="rt_smol")]
+                let tcp = __rt__::TcpListener::try_from(tcp)?;
+                #[cfg(feature="rt_glommio")]
+                // SAFETY: the fd is of a listening TCP socket, and owned only by this
+                let tcp = unsafe {std::os::fd::FromRawFd::from_raw_fd(std::os::fd::IntoRawFd::into_raw_fd(tcp))};
+
+                Ok(Self::Tcp(tcp))
+            }
+
+            #[cfg(unix)]
+            Socket::Unix(unix) => {
+                unix.set_nonblocking(true)?;
+
+                #[cfg(feature="rt_tokio")]
+                let unix = __rt__::UnixListener::from_std(unix)?;
+                #[cfg(feature="rt_async-std")]
+                let unix = __rt__::UnixListener::from(unix);
+                #[cfg(feature="rt_smol")]
+                let unix = __rt__::UnixListener::try_from(unix)?;
+
+                #[cfg(not(feature="rt_glommio"))] {
+                    Ok(Self::Unix(unix, None))
+                }
+                #[cfg(feature="rt_glommio")] {
+                    drop(unix);
+                    Err(io::Error::new(io::ErrorKind::Unsupported, "glommio can't serve on a Unix domain socket not bound by itself"))
+                }
+            }
+        }
+    }
+
+    #[cfg(unix)]
+    /// Bind a Unix domain socket at `path`, removing a stale socket file
+    /// left by a previous process ( no one is listening on it )
+    pub(crate) async fn bind_unix(path: &std::path::Path) -> io::Result<Self> {
+        use std::os::unix::fs::FileTypeExt;
+
+        if std::fs::symlink_metadata(path).is_ok_and(|m| m.file_type().is_socket())
+        && std::os::unix::net::UnixStream::connect(path).is_err() {
+            crate::DEBUG!("Removing stale socket file `{}`", path.display());
+            std::fs::remove_file(path)?;
+        }
+
+        #[cfg(feature="rt_async-std")]
+        let listener = __rt__::UnixListener::bind(path).await?;
+        #[cfg(any(feature="rt_tokio", feature="rt_smol", feature="rt_glommio"))]
+        let listener = __rt__::UnixListener::bind(path)?;
+
+        Ok(Self::Unix(listener, Some(path.to_path_buf())))
+    }
+
+    pub(crate) async fn accept(&self) -> io::Result<(Connection, PeerAddr)> {
+        match self {
+            #[cfg(any(feature="rt_tokio", feature="rt_async-std", feature="rt_smol"))]
+            Self::Tcp(listener) => {
+                let (connection, addr) = listener.accept().await?;
+                Ok((connection.into(), PeerAddr::Tcp(addr)))
+            }
+            #[cfg(feature="rt_glommio")]
+            Self::Tcp(listener) => {
+                let connection = listener.accept().await?;
+                let addr = connection.peer_addr()?;
+                Ok((connection.into(), PeerAddr::Tcp(addr)))
+            }
+
+            #[cfg(all(unix, any(feature="rt_tokio", feature="rt_async-std", feature="rt_smol")))]
+            Self::Unix(listener, _) => {
+                let (connection, addr) = listener.accept().await?;
+                let path = addr.as_pathname().map(std::path::Path::to_path_buf);
+                Ok((connection.into(), PeerAddr::Unix(path)))
+            }
+            #[cfg(all(unix, feature="rt_glommio"))]
+            Self::Unix(listener, _) => {
+                let connection = listener.accept().await?;
+                let path = connection.peer_addr()?.as_pathname().map(std::path::Path::to_path_buf);
+                Ok((connection.into(), PeerAddr::Unix(path)))
+            }
+        }
+    }
+
+    /// Accept a connection on any of `listeners`, polling them from
+    /// the one next to the last accepted, so that a busy listener
+    /// doesn't starve the others
+    pub(crate) async fn accept_any(listeners: &[Self], next: &AtomicUsize) -> io::Result<(Connection, PeerAddr)> {
+        use std::{future::Future, task::Poll};
+
+        if let [listener] = listeners {
+            return listener.accept().await
+        }
+
+        let mut accepts = listeners.iter()
+            .map(|listener| Box::pin(listener.accept()))
+            .collect::<Vec<_>>();
+        let start = next.load(Ordering::Relaxed) % accepts.len();
+        std::future::poll_fn(|cx| {
+            for i in (start..accepts.len()).chain(0..start) {
+                if let Poll::Ready(accepted) = accepts[i].as_mut().poll(cx) {
+                    next.store(i + 1, Ordering::Relaxed);
+                    return Poll::Ready(accepted)
+                }
+            }
+            Poll::Pending
+        }).await
+    }
+}
+
+#[cfg(unix)]
+impl Drop for RtListener {
+    fn drop(&mut self) {
+        if let Self::Unix(_, Some(path)) = self {
+            let _ = std::fs::remove_file(path);
+        }
+    }
+}
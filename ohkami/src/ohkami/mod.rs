@@ -2,11 +2,25 @@
 
 #[cfg(test)]
 mod _test;
+#[cfg(test)]
+mod _test_howl;
+#[cfg(test)]
+pub(crate) mod _test_util;
 
 pub(crate) mod build;
 pub(crate) mod router;
+pub(crate) mod panic;
+
+#[cfg(feature="__rt_native__")]
+mod listener;
+#[cfg(feature="__rt_native__")]
+use listener::RtListener;
+#[cfg(feature="__rt_native__")]
+pub use listener::Listener;
 
 pub use build::{Route, Routes};
+pub use panic::Panic;
+pub(crate) use panic::PanicHook;
 
 use crate::fang::Fangs;
 use std::sync::Arc;
@@ -108,7 +122,15 @@ use {crate::{__rt__, Session}, ohkami_lib::signal};
 /// #### path_params：
 /// A tuple of types that implement `FromParam` trait.\
 /// If the path contains only one parameter, then you can omit the tuple.\
-/// (In current ohkami, at most *2* path params can be handled.)
+/// (In current ohkami, at most *8* path params can be handled.)
+/// 
+/// Instead, `format::Path<T>` deserializes them into `T` by their names in
+/// the route, as a `FromRequest` item.
+/// 
+/// A route can end with a wildcard section like `/assets/*path`, matching
+/// all the rest of path ( one or more characters ) as the last path param.
+/// Static and param routes take priority over it, so it also works as a
+/// fallback e.g. `"/*path".GET(spa_index)`.
 /// 
 /// <br>
 /// 
@@ -228,6 +250,71 @@ impl Ohkami {
         }
     }
 
+    /// Respond with `hook` to a request whose handling panicked,
+    /// instead of `500 Internal Server Error`.
+    /// 
+    /// A panic at any point of the fangs and the handler, including after
+    /// their `.await`s, is caught and passed to `hook` with the request's
+    /// method and path. The connection is kept serving other requests.
+    /// 
+    /// *note* : Only the hook of the `Ohkami` that `howl`s is used;
+    /// ones of `Ohkami`s nested by `By` are ignored.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Ohkami::new((
+    ///         "/".GET(|| async {"Hello, world!"}),
+    ///     )).on_panic(|panic| {
+    ///         eprintln!("panicked in {} {}: {:?}", panic.method(), panic.path(), panic.message());
+    ///         Response::ServiceUnavailable().with_text("Please try again later")
+    ///     }).howl("localhost:3000").await
+    /// }
+    /// ```
+    pub fn on_panic(mut self, hook: impl Fn(&Panic) -> crate::Response + Send + Sync + 'static) -> Self {
+        self.routes.panic_hook = Some(PanicHook::new(hook));
+        self
+    }
+
+    /// Answer an `OPTIONS` request, that isn't a CORS preflight, with
+    /// `204 No Content` and `Allow` header listing the methods routed
+    /// for the path, instead of `404 Not Found`.
+    /// 
+    /// *note* : Only the setting of the `Ohkami` that `howl`s is used,
+    /// like `on_panic`.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```
+    /// use ohkami::prelude::*;
+    /// use ohkami::testing::*;
+    /// 
+    /// # #[tokio::main]
+    /// # async fn main() {
+    /// let t = Ohkami::new((
+    ///     "/users".GET(|| async {"users"}).POST(|| async {"created"}),
+    /// )).answer_options().test();
+    /// 
+    /// let res = t.oneshot(TestRequest::OPTIONS("/users")).await;
+    /// assert_eq!(res.status(), Status::NoContent);
+    /// assert_eq!(res.header("Allow"), Some("GET, POST, HEAD, OPTIONS"));
+    /// # }
+    /// ```
+    pub fn answer_options(mut self) -> Self {
+        self.routes.answer_options = true;
+        self
+    }
+
     #[cfg(feature="__rt_native__")]
     /// Start serving at `address`!
     /// 
@@ -238,8 +325,8 @@ impl Ohkami {
     /// - `smol::net::AsyncToSocketAddrs` if using `smol`
     /// - `std::net::ToSocketAddrs` if using `glommio`
     /// 
-    /// *note* : Keep-Alive timeout is 42 seconds and this is not
-    /// configureable by user (it'll be in future version...)
+    /// *note* : Serving with `ServerConfig::default()`.
+    /// Use `howl_with` to configure keep-alive timeout, limits and so on.
     /// 
     /// <br>
     /// 
@@ -291,141 +378,427 @@ impl Ohkami {
     /// }
     /// ```
     pub async fn howl(self, address: impl __rt__::ToSocketAddrs) {
-        let router = Arc::new(self.into_router().into_radix());
+        self.howl_with(crate::ServerConfig::default(), address).await
+    }
 
+    #[cfg(all(feature="tls", feature="__rt_native__"))]
+    /// Start serving HTTPS at `address` with `tls_config`!
+    /// 
+    /// Accepted connections are wrapped by rustls, and the negotiated
+    /// session is available to handlers as `&ohkami::tls::TlsInfo`.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// use ohkami::tls::TlsConfig;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let tls_config = TlsConfig::from_pem_files(
+    ///         "./certs/server.pem",
+    ///         "./certs/server.key",
+    ///     ).expect("Failed to load certificate");
+    /// 
+    ///     Ohkami::new((
+    ///         "/".GET(|| async {"Hello, HTTPS!"}),
+    ///     )).howls("0.0.0.0:443", tls_config).await
+    /// }
+    /// ```
+    pub async fn howls(self, address: impl __rt__::ToSocketAddrs, tls_config: crate::tls::TlsConfig) {
+        self.howl_with(crate::ServerConfig::default().tls(tls_config), address).await
+    }
+
+    #[cfg(feature="__rt_native__")]
+    /// Start serving at `address` with `config`!
+    /// 
+    /// See `ServerConfig` for available items and their defaults.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// use ohkami::ServerConfig;
+    /// use std::time::Duration;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let public_api = Ohkami::new((
+    ///         "/".GET(|| async {"Hello, world!"}),
+    ///     )).howl_with(
+    ///         ServerConfig::new()
+    ///             .payload_limit(1 << 16)
+    ///             .max_connections(10000),
+    ///         "0.0.0.0:8080",
+    ///     );
+    /// 
+    ///     let admin_api = Ohkami::new((
+    ///         "/upload".PUT(|| async {"uploaded"}),
+    ///     )).howl_with(
+    ///         ServerConfig::new()
+    ///             .payload_limit(1 << 30)
+    ///             .keepalive_timeout(Duration::from_secs(300)),
+    ///         "127.0.0.1:9090",
+    ///     );
+    /// 
+    ///     tokio::join!(public_api, admin_api);
+    /// }
+    /// ```
+    pub async fn howl_with(self, config: crate::ServerConfig, address: impl __rt__::ToSocketAddrs) {
+        self.howl_with_shutdown(config, address, signal::shutdown()).await
+    }
+
+    #[cfg(feature="__rt_native__")]
+    /// Start serving at `address` with `config` until `signal` completes!
+    /// 
+    /// `howl` and `howl_with` shut down on SIGINT ( Ctrl-C ) or SIGTERM ( only Ctrl-C
+    /// on non-unix platforms ); this is for any other trigger like a channel or an admin endpoint.
+    /// 
+    /// On `signal`, the server stops accepting connections and lets in-flight
+    /// requests finish, responding with `Connection: close` and closing idle
+    /// keep-alive connections. Sessions still open after `shutdown_timeout`
+    /// of `config` are closed forcibly.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// use ohkami::ServerConfig;
+    /// use std::time::Duration;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    /// 
+    ///     tokio::spawn(async move {
+    ///         tokio::time::sleep(Duration::from_secs(60)).await;
+    ///         shutdown_tx.send(()).unwrap();
+    ///     });
+    /// 
+    ///     Ohkami::new((
+    ///         "/".GET(|| async {"Hello, world!"}),
+    ///     )).howl_with_shutdown(
+    ///         ServerConfig::new().shutdown_timeout(Duration::from_secs(10)),
+    ///         "0.0.0.0:8080",
+    ///         async {let _ = shutdown_rx.await;},
+    ///     ).await
+    /// }
+    /// ```
+    pub async fn howl_with_shutdown(self,
+        config:  crate::ServerConfig,
+        address: impl __rt__::ToSocketAddrs,
+        signal:  impl std::future::Future<Output = ()>,
+    ) {
         #[cfg(any(feature="rt_tokio",feature="rt_async-std",feature="rt_smol"))]
         let listener = __rt__::TcpListener::bind(address).await.expect("Failed to bind TCP listener");
         #[cfg(any(feature="rt_glommio"))]
         let listener = __rt__::TcpListener::bind(address).expect("Failed to bind TCP listener");
-        
-        let (close_tx, close_rx) = signal::watch::channel(());
-
-        let ctrl_c = signal::ctrl_c();
-        let (ctrl_c_tx, ctrl_c_rx) = signal::watch::channel(());
-
-        #[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
-        __rt__::spawn(async {
-            ctrl_c.await.expect("Something was wrong around Ctrl-C");
-            drop(ctrl_c_rx);
-        });
-        #[cfg(any(feature="rt_smol",feature="rt_glommio"))]
-        __rt__::spawn(async {
-            ctrl_c.await.expect("Something was wrong around Ctrl-C");
-            drop(ctrl_c_rx);
-        }).detach();
 
-        #[cfg(feature="rt_tokio")] {
-            loop {
-                __rt__::select! {
-                    accept = listener.accept() => {
-                        let Ok((connection, addr)) = accept else {continue};
-
-                        let session = Session::new(
-                            router.clone(),
-                            connection,
-                            addr.ip()
-                        );
-
-                        let close_rx = close_rx.clone();
-                        __rt__::spawn(async {
-                            session.manage().await;
-                            drop(close_rx)
-                        });
-                    }
-                    _ = ctrl_c_tx.closed() => {
-                        crate::DEBUG!("Recieved Ctrl-C, trying graceful shutdown");
-                        drop(listener);
-                        break
-                    }
-                }
+        self.serve(config, vec![RtListener::Tcp(listener)], signal).await
+    }
+
+    #[cfg(feature="__rt_native__")]
+    /// Start serving on pre-bound `listeners` with `config`!
+    /// 
+    /// For sockets bound in advance ( e.g. at port `0` to know the
+    /// actual address via `Listener::local_addr` ), handed over from
+    /// another process, or passed by systemd socket activation
+    /// ( `Listener::from_systemd` ). Connections from all of them
+    /// are served by the same routes and `config`.
+    /// 
+    /// Shuts down on SIGINT ( Ctrl-C ) or SIGTERM like `howl_with`.
+    /// 
+    /// *note* : Panics when `listeners` is empty.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// use ohkami::{Listener, ServerConfig};
+    /// 
+    /// #[tokio::main]
+    /// async fn main() -> std::io::Result<()> {
+    ///     let mut listeners = Listener::from_systemd()?;
+    ///     if listeners.is_empty() {
+    ///         listeners.push(Listener::bind("0.0.0.0:8080")?);
+    ///     }
+    /// 
+    ///     Ohkami::new((
+    ///         "/".GET(|| async {"Hello, world!"}),
+    ///     )).howl_on(ServerConfig::new(), listeners).await;
+    /// 
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn howl_on(self, config: crate::ServerConfig, listeners: impl IntoIterator<Item = Listener>) {
+        let listeners = listeners.into_iter()
+            .map(|listener| RtListener::from_listener(listener).expect("Failed to serve on the listener"))
+            .collect::<Vec<_>>();
+        assert!(!listeners.is_empty(), "No listener to serve on");
+
+        self.serve(config, listeners, signal::shutdown()).await
+    }
+
+    #[cfg(all(unix, feature="__rt_native__"))]
+    /// Start serving at a Unix domain socket `path`!
+    /// 
+    /// A stale socket file left at `path` is removed before binding,
+    /// and the socket file is removed after shutdown. The peer is
+    /// available to handlers as `Request::peer` ( `PeerAddr::Unix` ).
+    /// 
+    /// *note* : Serving with `ServerConfig::default()`.
+    /// Use `howl_unix_with` to configure. `tls` of the config is not
+    /// applied to a Unix domain socket.
+    /// 
+    /// <br>
+    /// 
+    /// ---
+    /// 
+    /// *example.rs*
+    /// ```no_run
+    /// use ohkami::prelude::*;
+    /// 
+    /// #[tokio::main]
+    /// async fn main() {
+    ///     Ohkami::new((
+    ///         "/".GET(|| async {"Hello, world!"}),
+    ///     )).howl_unix("/run/app/app.sock").await
+    /// }
+    /// ```
+    pub async fn howl_unix(self, path: impl AsRef<std::path::Path>) {
+        self.howl_unix_with(crate::ServerConfig::default(), path).await
+    }
+
+    #[cfg(all(unix, feature="__rt_native__"))]
+    /// Start serving at a Unix domain socket `path` with `config`!
+    /// 
+    /// See `howl_unix` and `ServerConfig` for details.
+    pub async fn howl_unix_with(self, config: crate::ServerConfig, path: impl AsRef<std::path::Path>) {
+        let listener = RtListener::bind_unix(path.as_ref()).await.expect("Failed to bind Unix domain socket");
+
+        self.serve(config, vec![listener], signal::shutdown()).await
+    }
+
+    #[cfg(feature="__rt_native__")]
+    async fn serve(self,
+        config:    crate::ServerConfig,
+        listeners: Vec<RtListener>,
+        signal:    impl std::future::Future<Output = ()>,
+    ) {
+        use crate::session::{Connection, Shutdown};
+
+        let router = Arc::new(self.into_router().into_radix());
+        let config = Arc::new(config);
+
+        #[cfg(feature="tls")]
+        let tls_acceptor = config.tls.as_ref().map(crate::tls::TlsConfig::acceptor);
+
+        let connection_limit = config.max_connections.map(|max| Arc::new(signal::Semaphore::new(max)));
+
+        let (shutdown_tx, shutdown_rx) = signal::watch::channel(Shutdown::None);
+        let mut signal = std::pin::pin!(signal);
+
+        /* rejections in flight are bounded too, beyond which connections are just closed */
+        let rejection_limit = config.max_connections
+            .filter(|_| config.reject_over_limit)
+            .map(|max| Arc::new(signal::Semaphore::new(max)));
+
+        let spawn_rejection = |connection: Connection, _peer: crate::PeerAddr| {
+            let Some(rejecting) = rejection_limit.as_ref().and_then(|limit| limit.clone().try_acquire_owned().ok()) else {
+                crate::DEBUG!("Closing connection from {_peer} over `max_connections` and rejections in flight");
+                return drop(connection)
+            };
+
+            #[cfg(feature="tls")]
+            if tls_acceptor.is_some() && matches!(connection, Connection::Tcp(_)) {
+                /* no handshake for a connection to be rejected, and a TLS client can't read a plain `503` */
+                crate::DEBUG!("Closing TLS connection from {_peer} over `max_connections`");
+                return drop((connection, rejecting))
             }
-        }
 
-        #[cfg(feature="rt_async-std")] {
-            loop {
-                __rt__::select! {
-                    accept = __rt__::FutureExt::fuse(listener.accept()) => {
-                        let Ok((connection, addr)) = accept else {continue};
-
-                        let session = Session::new(
-                            router.clone(),
-                            connection,
-                            addr.ip()
-                        );
-
-                        let close_rx = close_rx.clone();
-                        __rt__::spawn(async {
-                            session.manage().await;
-                            drop(close_rx)
-                        });
+            let timeout     = config.response_write_timeout;
+            let shutdown_rx = shutdown_rx.clone();
+
+            /* without reading the PROXY header: the proxy relays `503` regardless */
+            let rejection = async move {
+                let mut connection = connection;
+
+                crate::DEBUG!("Rejecting connection from {_peer} over `max_connections`");
+                let mut res = crate::Response::ServiceUnavailable();
+                res.headers.set().Connection("close");
+                match crate::util::timeout_in(timeout, res.send(&mut connection)).await {
+                    Some(Ok(_))   => (),
+                    Some(Err(_e)) => {crate::DEBUG!("Failed to send `503 Service Unavailable` to {_peer}: {_e}");}
+                    None          => {crate::DEBUG!("Timed out sending `503 Service Unavailable` to {_peer}");}
+                }
+
+                drop((shutdown_rx, rejecting))
+            };
+
+            #[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+            __rt__::spawn(rejection);
+            #[cfg(any(feature="rt_smol",feature="rt_glommio"))]
+            __rt__::spawn(rejection).detach();
+        };
+
+        let spawn_session = |connection: Connection, peer: crate::PeerAddr, permit: Option<signal::OwnedSemaphorePermit>| {
+            /* limited but not permitted with `reject_over_limit` */
+            if connection_limit.is_some() && permit.is_none() {
+                return spawn_rejection(connection, peer)
+            }
+            let open = config.open_connections.open();
+
+            let router      = router.clone();
+            let config      = config.clone();
+            let shutdown_rx = shutdown_rx.clone();
+
+            #[cfg(feature="tls")]
+            let tls_acceptor = tls_acceptor.clone();
+
+            let session = async move {
+                let mut connection = connection;
+
+                let forced = || {
+                    let mut shutdown_rx = shutdown_rx.clone();
+                    async move {let _ = shutdown_rx.wait_for(|s| *s == Shutdown::Forced).await;}
+                };
+
+                /* PROXY header and TLS handshake are bounded like reading a request head */
+                let (peer, proxy) = if config.proxy_protocol {
+                    let read = crate::util::timeout_in(config.header_read_timeout, crate::session::proxy::read(&mut connection));
+                    match crate::util::until(forced(), read).await.flatten() {
+                        Some(Ok(proxy)) => (
+                            proxy.source().map_or(peer, crate::PeerAddr::Tcp),
+                            Some(Arc::new(proxy)),
+                        ),
+                        Some(Err(_e)) => {
+                            crate::DEBUG!("Invalid PROXY header from {peer}: {_e}");
+                            return drop((shutdown_rx, permit, open))
+                        }
+                        None => {
+                            crate::DEBUG!("PROXY header from {peer} timed out");
+                            return drop((shutdown_rx, permit, open))
+                        }
                     }
-                    _ = __rt__::FutureExt::fuse(ctrl_c_tx.closed()) => {
-                        crate::DEBUG!("Recieved Ctrl-C, trying graceful shutdown");
-                        drop(listener);
-                        break
+                } else {
+                    (peer, None)
+                };
+
+                #[cfg(feature="tls")]
+                let connection = match (tls_acceptor, connection) {
+                    (Some(acceptor), Connection::Tcp(tcp)) => {
+                        let handshake = crate::util::timeout_in(config.header_read_timeout, acceptor.accept(tcp));
+                        match crate::util::until(forced(), handshake).await.flatten() {
+                            Some(Ok(tls)) => tls.into(),
+                            Some(Err(_e)) => {
+                                crate::DEBUG!("TLS handshake with {peer} failed: {_e}");
+                                return drop((shutdown_rx, permit, open))
+                            }
+                            None => {
+                                crate::DEBUG!("TLS handshake with {peer} timed out");
+                                return drop((shutdown_rx, permit, open))
+                            }
+                        }
                     }
+                    (_, connection) => connection,
+                };
+
+                /* dropping the session closes the connection */
+                crate::util::until(forced(), Session::new(
+                    router,
+                    connection,
+                    peer,
+                    config,
+                    shutdown_rx.clone(),
+                    proxy,
+                ).manage()).await;
+
+                drop((shutdown_rx, permit, open))
+            };
+
+            #[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+            __rt__::spawn(session);
+            #[cfg(any(feature="rt_smol",feature="rt_glommio"))]
+            __rt__::spawn(session).detach();
+        };
+
+        let next_listener = std::sync::atomic::AtomicUsize::new(0);
+        let accept = || async {
+            let accept_any = || RtListener::accept_any(&listeners, &next_listener);
+            let (accepted, permit) = match &connection_limit {
+                None => (accept_any().await, None),
+                Some(limit) if config.reject_over_limit => {
+                    let accepted = accept_any().await;
+                    (accepted, limit.clone().try_acquire_owned().ok())
                 }
+                Some(limit) => {
+                    let permit = limit.clone().acquire_owned().await.expect("Connection limit is never closed");
+                    (accept_any().await, Some(permit))
+                }
+            };
+            if let Err(_e) = &accepted {
+                crate::DEBUG!("Failed to accept a connection: {_e}");
+                /* errors like `EMFILE` persist for a while, so back off not to busy-loop */
+                drop(permit);
+                __rt__::sleep(std::time::Duration::from_millis(100)).await;
+                return (accepted, None)
             }
-        }
-        
-        #[cfg(feature="rt_smol")] {
+            (accepted, permit)
+        };
+
+        #[cfg(feature="rt_tokio")] {
             loop {
                 __rt__::select! {
-                    accept = __rt__::FutureExt::fuse(listener.accept()) => {
-                        let Ok((connection, addr)) = accept else {continue};
-
-                        let session = Session::new(
-                            router.clone(),
-                            connection,
-                            addr.ip()
-                        );
-
-                        let close_rx = close_rx.clone();
-                        __rt__::spawn(async {
-                            session.manage().await;
-                            drop(close_rx)
-                        }).detach();
+                    accept = accept() => {
+                        let (Ok((connection, peer)), permit) = accept else {continue};
+                        spawn_session(connection, peer, permit);
                     }
-                    _ = __rt__::FutureExt::fuse(ctrl_c_tx.closed()) => {
-                        crate::DEBUG!("Recieved Ctrl-C, trying graceful shutdown");
-                        drop(listener);
+                    _ = &mut signal => {
+                        crate::DEBUG!("Recieved shutdown signal, trying graceful shutdown");
                         break
                     }
                 }
             }
         }
 
-        #[cfg(feature="rt_glommio")] {
+        #[cfg(any(feature="rt_async-std",feature="rt_smol",feature="rt_glommio"))] {
             loop {
                 __rt__::select! {
-                    accept = __rt__::FutureExt::fuse(listener.accept()) => {
-                        let Ok(connection) = accept else {continue};
-                        let Ok(addr) = connection.peer_addr() else {continue};
-        
-                        let session = Session::new(
-                            router.clone(),
-                            connection,
-                            addr.ip()
-                        );
-
-                        let close_rx = close_rx.clone();
-                        __rt__::spawn(async {
-                            session.manage().await;
-                            drop(close_rx)
-                        }).detach();
+                    accept = __rt__::FutureExt::fuse(accept()) => {
+                        let (Ok((connection, peer)), permit) = accept else {continue};
+                        spawn_session(connection, peer, permit);
                     }
-                    _ = __rt__::FutureExt::fuse(ctrl_c_tx.closed()) => {
-                        crate::DEBUG!("Recieved Ctrl-C, trying graceful shutdown");
-                        drop(listener);
+                    _ = __rt__::FutureExt::fuse(&mut signal) => {
+                        crate::DEBUG!("Recieved shutdown signal, trying graceful shutdown");
                         break
                     }
                 }
             }
         }
 
-        crate::DEBUG!("Waiting {} session(s) to finish...", close_tx.receiver_count());
-        drop(close_rx);
-        close_tx.closed().await;
+        drop(listeners);
+
+        shutdown_tx.send_replace(Shutdown::Graceful);
+        drop(shutdown_rx);
+
+        crate::DEBUG!("Waiting {} session(s) to finish...", shutdown_tx.receiver_count());
+        if crate::util::timeout_in(config.shutdown_timeout, shutdown_tx.closed()).await.is_none() {
+            crate::warning!("[WARNING] Closing {} session(s) still open after `shutdown_timeout`", shutdown_tx.receiver_count());
+            shutdown_tx.send_replace(Shutdown::Forced);
+            shutdown_tx.closed().await;
+        }
     }
 
     #[cfg(feature="rt_worker")]
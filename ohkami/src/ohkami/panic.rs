@@ -0,0 +1,75 @@
+use crate::{Method, Request, Response};
+use std::any::Any;
+use std::future::Future;
+use std::panic::{AssertUnwindSafe, catch_unwind};
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+
+/// A panic in handling a request, passed to the hook registered by `Ohkami::on_panic`
+pub struct Panic<'p> {
+    payload: &'p (dyn Any + Send),
+    method:  Method,
+    path:    &'p str,
+}
+impl Panic<'_> {
+    /// Panic payload, `&str` or `String` for `panic!` with a message
+    pub fn payload(&self) -> &(dyn Any + Send) {
+        self.payload
+    }
+    /// Message of `panic!`, `None` when panicked with a payload of other type
+    pub fn message(&self) -> Option<&str> {
+        self.payload.downcast_ref::<&str>().copied()
+            .or_else(|| self.payload.downcast_ref::<String>().map(String::as_str))
+    }
+    pub fn method(&self) -> Method {
+        self.method
+    }
+    pub fn path(&self) -> &str {
+        self.path
+    }
+}
+
+#[derive(Clone)]
+pub(crate) struct PanicHook(Arc<dyn Fn(&Panic) -> Response + Send + Sync>);
+impl PanicHook {
+    pub(crate) fn new(hook: impl Fn(&Panic) -> Response + Send + Sync + 'static) -> Self {
+        Self(Arc::new(hook))
+    }
+}
+impl std::fmt::Debug for PanicHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("PanicHook")
+    }
+}
+
+/// Response for a panic in handling `req`, by `hook` or `500 Internal Server Error`
+#[cold] #[inline(never)]
+pub(crate) fn panicking(hook: Option<&PanicHook>, payload: Box<dyn Any + Send>, req: &Request) -> Response {
+    let panic = Panic { payload: &*payload, method: req.method, path: &req.path };
+
+    match panic.message() {
+        Some(msg) => crate::warning!("[Panicked] {} {}: {msg}", panic.method, panic.path),
+        None      => crate::warning!("[Panicked] {} {}", panic.method, panic.path),
+    }
+
+    match hook {
+        Some(PanicHook(hook)) => hook(&panic),
+        None                  => Response::InternalServerError(),
+    }
+}
+
+/// Polls the inner future under `catch_unwind`, not only its first poll
+pub(crate) struct CatchUnwind<F>(pub(crate) F);
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // SAFETY: `self.0` is never moved out
+        let inner = unsafe {self.map_unchecked_mut(|this| &mut this.0)};
+        match catch_unwind(AssertUnwindSafe(|| inner.poll(cx))) {
+            Ok(poll)   => poll.map(Ok),
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}
@@ -32,6 +32,9 @@ impl RouteSections {
                 Err(e) => panic!("{e}: `{route}`"),
                 Ok(rs) => rs,
             };
+            if sections.back().is_some_and(RouteSection::is_wildcard) {
+                panic!("Wildcard section must be the last one: `{route}`")
+            }
             sections.push_back(section)
         }
 
@@ -41,6 +44,15 @@ impl RouteSections {
     pub(crate)  fn literal(&self) -> &'static str {
         self.litreral
     }
+
+    pub(crate) fn ends_with_wildcard(&self) -> bool {
+        self.sections.back().is_some_and(RouteSection::is_wildcard)
+    }
+
+    /// Names of the param and wildcard sections, in order
+    pub(crate) fn param_names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.sections.iter().filter_map(RouteSection::param_name)
+    }
 }
 const _: () = {
     impl IntoIterator for RouteSections {
@@ -53,11 +65,26 @@ const _: () = {
 #[derive(Clone)]
 pub enum RouteSection {
     Static(&'static [u8]),
-    Param,
+    /// `:name`
+    Param(&'static str),
+    /// `*name`, matching all the rest of path
+    Wildcard(&'static str),
 }
 impl RouteSection {
+    fn is_wildcard(&self) -> bool {
+        matches!(self, Self::Wildcard(_))
+    }
+
+    fn param_name(&self) -> Option<&'static str> {
+        match self {
+            Self::Param(name) | Self::Wildcard(name) => Some(name),
+            Self::Static(_) => None,
+        }
+    }
+
     pub(crate) fn new(section_bytes: &'static [u8]) -> Result<Self, String> {
-        let mut section_chars = std::str::from_utf8(section_bytes).unwrap().chars().peekable();
+        let section_str = std::str::from_utf8(section_bytes).unwrap();
+        let mut section_chars = section_str.chars().peekable();
 
         fn validate_section_name(mut name: Peekable<Chars>) -> Result<(), String> {
             let is_invalid_head_or_tail_char = |c: char| !/* NOT */ matches!(c,
@@ -92,7 +119,12 @@ impl RouteSection {
             Some(':') => {
                 let _/* colon */ = section_chars.next();
                 let _/* validation */ = validate_section_name(section_chars)?;
-                Ok(Self::Param)
+                Ok(Self::Param(&section_str[1..]))
+            },
+            Some('*') => {
+                let _/* asterisk */ = section_chars.next();
+                let _/* validation */ = validate_section_name(section_chars)?;
+                Ok(Self::Wildcard(&section_str[1..]))
             },
             _ => {
                 let _/* validation */ = validate_section_name(section_chars)?;
@@ -105,7 +137,8 @@ const _: () = {
     impl std::fmt::Debug for RouteSection {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
-                Self::Param         => f.write_str(":Param"),
+                Self::Param(name)    => write!(f, ":{name}"),
+                Self::Wildcard(name) => write!(f, "*{name}"),
                 Self::Static(bytes) => f.write_str(std::str::from_utf8(bytes).unwrap()),
             }
         }
@@ -1,6 +1,7 @@
 use crate::request::Path;
-use crate::{Method, Request, Response};
+use crate::{Method, Request, Response, Status};
 use crate::fang::{FangProcCaller, BoxedFPC};
+use crate::ohkami::panic::{CatchUnwind, panicking};
 use ohkami_lib::Slice;
 use std::fmt::Write as _;
 
@@ -13,13 +14,21 @@ pub(crate) struct RadixRouter {
     pub(super) PATCH:   Node,
     pub(super) DELETE:  Node,
     pub(super) OPTIONS: Node,
+    pub(super) extensions: Box<[(&'static str, Node)]>,
+
+    pub(crate) panic_hook: Option<crate::ohkami::PanicHook>,
+    /// set by `Ohkami::answer_options`
+    pub(crate) answer_options: bool,
 }
 
 pub(super) struct Node {
-    pub(super) patterns:  &'static [Pattern],
-    pub(super) children:  &'static [Node],
-    pub(super) proc:      BoxedFPC,
-    pub(super) __catch__: BoxedFPC,
+    pub(super) patterns:    &'static [Pattern],
+    pub(super) children:    &'static [Node],
+    /// names of the path params for `proc`, in order
+    pub(super) param_names: &'static [&'static str],
+    /// `None` when no handler is registered here
+    pub(super) proc:        Option<BoxedFPC>,
+    pub(super) __catch__:   BoxedFPC,
 } const _: () = {
     impl std::fmt::Debug for Node {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -41,11 +50,14 @@ pub(super) struct Node {
 pub(super) enum Pattern {
     Static(&'static [u8]),
     Param,
+    /// matching all the rest of path, always the last pattern of a leaf
+    Wildcard,
 } const _: () = {
     impl std::fmt::Debug for Pattern {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
                 Self::Param         => f.write_str(":Param"),
+                Self::Wildcard      => f.write_str("*Wildcard"),
                 Self::Static(bytes) => {
                     f.write_char('\'')?;
                     f.write_str(std::str::from_utf8(bytes).unwrap())?;
@@ -66,37 +78,164 @@ impl RadixRouter {
         &self,
         req: &mut Request,
     ) -> Response {
-        (match req.method {
-            Method::GET     => &self.GET,
-            Method::PUT     => &self.PUT,
-            Method::POST    => &self.POST,
-            Method::PATCH   => &self.PATCH,
-            Method::DELETE  => &self.DELETE,
-            Method::OPTIONS => &self.OPTIONS,
-            Method::HEAD => {
-                let mut res = self.GET.search(&mut req.path).call_bite(req).await;
-                {/* not `res.drop_content()` to leave `Content-Type`, `Content-Length` */
-                    res.content = crate::response::Content::None;
+        if let Err(res) = self.resolve_method(req) {
+            return res
+        }
+        let Some(node) = self.node(req.method) else {
+            return Response::NotImplemented()
+        };
+        let mut res = match node.search(&mut req.path) {
+            Ok(proc) => {
+                let mut res = proc.call_bite(req).await;
+                if req.method.isOPTIONS()
+                && self.answer_options
+                && res.status == Status::NotFound
+                && req.headers.AccessControlRequestMethod().is_none() {
+                    /* not a CORS preflight: answer it as a standard OPTIONS request */
+                    res.status = Status::NoContent;
+                    res.headers.set().Allow(self.allowed_methods(&req.path).join(", "));
+                }
+                res
+            }
+            Err(catcher) => {
+                let mut res = catcher.call_bite(req).await;
+                if res.status == Status::NotFound {
+                    let allowed_methods = self.allowed_methods(&req.path);
+                    if !allowed_methods.is_empty() {
+                        res.status = Status::MethodNotAllowed;
+                        res.headers.set().Allow(allowed_methods.join(", "));
+                    }
                 }
-                return res
+                res
+            }
+        };
+        if req.method.isHEAD() {
+            /* not `res.drop_content()` to leave `Content-Type`, `Content-Length` */
+            res.content = crate::response::Content::None;
+        }
+        res
+    }
+
+    /// `None` for an extension method not routed in this router
+    #[inline(always)]
+    fn node(&self, method: Method) -> Option<&Node> {
+        match method {
+            Method::GET     => Some(&self.GET),
+            Method::PUT     => Some(&self.PUT),
+            Method::POST    => Some(&self.POST),
+            Method::PATCH   => Some(&self.PATCH),
+            Method::DELETE  => Some(&self.DELETE),
+            Method::OPTIONS => Some(&self.OPTIONS),
+            Method::HEAD    => Some(&self.GET),
+            Method::Extension(name) => self.extensions.iter()
+                .find_map(|(n, node)| (*n == name).then_some(node)),
+        }
+    }
+
+    /// Set `req.method` to the extension method of the request routed
+    /// in this router, or `501 Not Implemented` when it's not
+    pub(crate) fn resolve_method(&self, req: &mut Request) -> Result<(), Response> {
+        if let Some(name) = req.extension_method.take() {
+            // SAFETY: `name` refers to the request bytes, alive while `req` is
+            req.method = self.extension_method(unsafe {name.as_bytes()})
+                .ok_or_else(Response::NotImplemented)?;
+        }
+        Ok(())
+    }
+
+    /// Extension method named `name` routed in this router, with the name
+    /// held by this router instead of the request bytes
+    #[inline]
+    fn extension_method(&self, name: &[u8]) -> Option<Method> {
+        self.extensions.iter()
+            .find_map(|(n, _)| (n.as_bytes() == name).then_some(Method::Extension(n)))
+    }
+
+    /// `handle` converting a panic at any point of it into a response
+    /// by `panic_hook` or `500 Internal Server Error`
+    pub(crate) async fn handle_catching(
+        &self,
+        req: &mut Request,
+    ) -> Response {
+        let panic = match CatchUnwind(self.handle(req)).await {
+            Ok(res)    => return res,
+            Err(panic) => panic,
+        };
+        panicking(self.panic_hook.as_ref(), panic, req)
+    }
+
+    /// Whether a handler is registered for `method` and `path`
+    pub(crate) fn routes(&self, method: Method, path: &Path) -> bool {
+        // SAFETY: `path` is alive while `find`
+        self.node(method).is_some_and(|node| node.find(unsafe {path.normalized_bytes()}, |_, _| ())
+            .is_ok_and(|found| found.proc.is_some()))
+    }
+
+    /// Methods that `path` is routed for, as listed in `Allow` header.
+    /// Empty when `path` isn't routed at all.
+    fn allowed_methods(&self, path: &Path) -> Vec<&'static str> {
+        let mut methods = Vec::new();
+        let extensions = self.extensions.iter().map(|(name, _)| Method::Extension(name));
+        for method in [Method::GET, Method::PUT, Method::POST, Method::PATCH, Method::DELETE].into_iter().chain(extensions) {
+            if self.routes(method, path) {
+                methods.push(method.as_str())
+            }
+        }
+        if !methods.is_empty() {
+            if methods.contains(&"GET") {
+                methods.push("HEAD")
             }
-        }).search(&mut req.path).call_bite(req).await
+            methods.push("OPTIONS")
+        }
+        methods
     }
 }
 
 impl Node {
+    /// `Ok(proc)` of the handler for `path`, or `Err(catcher)` when there's none
     #[inline]
-    pub(super/* for test */) fn search(&self,
+    fn search(&self,
         path: &mut Path
-    ) -> &dyn FangProcCaller {
+    ) -> Result<&dyn FangProcCaller, &dyn FangProcCaller> {
         // SAFETY:
         // 1. `req` must be alive while `search`
         // 2. `Request` DOESN'T have method that mutates `bytes`,
         //    So what `bytes` refers to is NEVER changed by any other process
         //    while `search`
-        let mut bytes = unsafe {path.normalized_bytes()};
+        let bytes = unsafe {path.normalized_bytes()};
 
+        match self.find(bytes, |i, param| unsafe {path.set_param(i, Slice::from_bytes(param))}) {
+            Ok(found) => match &found.proc {
+                Some(proc) => {path.set_param_names(found.param_names); Ok(proc)}
+                None       => Err(&found.__catch__),
+            },
+            Err(catcher) => Err(&catcher.__catch__),
+        }
+    }
+
+    /// `Ok(node)` matching whole `bytes`, or `Err(node)` whose `__catch__` handles it.
+    /// 
+    /// Static and param patterns are tried first, and the wildcard child of
+    /// the deepest node passed by is the fallback when they don't match.
+    #[inline]
+    fn find(&self,
+        mut bytes:     &[u8],
+        mut set_param: impl FnMut(usize, &[u8]),
+    ) -> Result<&Node, &Node> {
         let mut target = self;
+        let mut n_params = 0;
+
+        /* wildcard child, the rest of path to match it, and the number of params before it */
+        let mut fallback: Option<(&Node, &[u8], usize)> = None;
+        macro_rules! fall_back {
+            () => {match fallback {
+                Some((wildcard, rest, n_params)) => {
+                    set_param(n_params, &rest[1..]/* skip initial '/' */);
+                    Ok(wildcard)
+                }
+                None => Err(target)
+            }};
+        }
 
         #[cfg(feature="DEBUG")]
         println!("[path] '{}'", bytes.escape_ascii());
@@ -111,7 +250,7 @@ impl Node {
                 if bytes.is_empty() || unsafe {bytes.get_unchecked(0)} != &b'/' {
                     // At least one `pattern` to match is remaining
                     // but remaining `bytes` doesn't start with '/'
-                    return &target.__catch__
+                    return fall_back!()
                 }
 
                 bytes = unsafe {bytes.get_unchecked(1..)};
@@ -122,28 +261,46 @@ impl Node {
                 match pattern {
                     Pattern::Static(s) => bytes = match bytes.strip_prefix(*s) {
                         Some(remaining) => remaining,
-                        None            => return &target.__catch__,
+                        None            => return fall_back!(),
                     },
                     Pattern::Param => {
                         let (param, remaining) = split_next_section(bytes);
-                        unsafe {path.push_param(Slice::from_bytes(param))}
+                        set_param(n_params, param);
+                        n_params += 1;
                         bytes = remaining;
                     },
+                    Pattern::Wildcard => {
+                        if bytes.is_empty() {
+                            return fall_back!()
+                        }
+                        set_param(n_params, bytes);
+                        n_params += 1;
+                        bytes = &[];
+                    }
                 }
             }
 
             if bytes.is_empty() {
                 #[cfg(feature="DEBUG")]
                 println!("Found: {target:?}");
-        
-                return  &target.proc
+
+                return match target.proc {
+                    Some(_) => Ok(target),
+                    None    => fall_back!().or(Ok(target)),
+                }
             } else {
                 #[cfg(feature="DEBUG")]
                 println!("not found, searching children: {:#?}", target.children);
+
+                if let Some(wildcard) = target.wildcard_child() {
+                    if bytes.len() > 1 {
+                        fallback = Some((wildcard, bytes, n_params));
+                    }
+                }
         
                 target = match target.matchable_child(bytes) {
                     Some(child) => child,
-                    None        => return &target.__catch__,
+                    None        => return fall_back!(),
                 }
             }
         }
@@ -162,12 +319,18 @@ impl Node {
         }
         None
     }
+
+    /// Child matching all the rest of path, sorted to the last
+    #[inline] fn wildcard_child(&self) -> Option<&Node> {
+        self.children.last().filter(|child| matches!(child.patterns, [Pattern::Wildcard]))
+    }
 }
 
 impl Pattern {
     #[inline(always)] fn is_matchable_to(&self, path: &[u8]) -> bool {
         match self {
             Self::Param     => true,
+            Self::Wildcard  => path.len() > 1,
             Self::Static(s) => (&path[1..]/* skip initial '/' */).starts_with(s),
         }
     }
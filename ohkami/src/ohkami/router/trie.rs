@@ -14,6 +14,13 @@ pub struct TrieRouter {
     pub(super) PATCH:   Node,
     pub(super) DELETE:  Node,
     pub(super) OPTIONS: Node,
+    /// trees of extension methods
+    pub(super) extensions: Vec<(&'static str, Node)>,
+
+    /// set by `Ohkami::on_panic`
+    pub(crate) panic_hook: Option<crate::ohkami::PanicHook>,
+    /// set by `Ohkami::answer_options`
+    pub(crate) answer_options: bool,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -59,11 +66,13 @@ pub(super) struct Node {
 pub(super) enum Pattern {
     Static(Cow<'static, [u8]>),
     Param,
+    Wildcard,
 } const _: () = {
     impl std::fmt::Debug for Pattern {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             match self {
                 Self::Param     => f.write_str(":Param"),
+                Self::Wildcard  => f.write_str("*Wildcard"),
                 Self::Static(v) => f.write_str(&format!(
                     "'{}'", std::str::from_utf8(&v).unwrap()
                 )),
@@ -78,6 +87,10 @@ pub(super) enum Pattern {
                     Self::Param => true,
                     _ => false,
                 }
+                Self::Wildcard => match other {
+                    Self::Wildcard => true,
+                    _ => false,
+                }
                 Self::Static(this_bytes) => {
                     match other {
                         Self::Static(other_bytes) => this_bytes == other_bytes,
@@ -91,7 +104,8 @@ pub(super) enum Pattern {
     impl From<RouteSection> for Pattern {
         fn from(section: RouteSection) -> Self {
             match section {
-                RouteSection::Param         => Pattern::Param,
+                RouteSection::Param(_)      => Pattern::Param,
+                RouteSection::Wildcard(_)   => Pattern::Wildcard,
                 RouteSection::Static(bytes) => Pattern::Static(Cow::Borrowed(bytes))
             }
         }
@@ -167,6 +181,10 @@ impl TrieRouter {
             PATCH:   Node::root(),
             DELETE:  Node::root(),
             OPTIONS: Node::root(),
+            extensions: Vec::new(),
+
+            panic_hook:     None,
+            answer_options: false,
         }
     }
 
@@ -175,7 +193,7 @@ impl TrieRouter {
     }
 
     pub(crate) fn register_handlers(&mut self, handlers: Handlers) {
-        let Handlers { route, GET, PUT, POST, PATCH, DELETE } = handlers;
+        let Handlers { route, GET, PUT, POST, PATCH, DELETE, extensions } = handlers;
 
         let methods = if !self.routes.insert(route.literal()) {
             panic!("Duplicate routes registration: `{}`", route.literal())
@@ -188,6 +206,7 @@ impl TrieRouter {
                             methods.push(stringify!($method))
                         }
                     )*
+                    methods.extend(extensions.iter().map(|(name, _)| *name));
                     methods
                 }}
             } allow_methods! { GET, PUT, POST, PATCH, DELETE }
@@ -195,12 +214,18 @@ impl TrieRouter {
 
         macro_rules! register {
             ($( $method:ident ),*) => {$(
-                if let Some(h) = $method {
+                if let Some(mut h) = $method {
+                    h.param_names = route.param_names().collect();
                     self.$method.register_handler(route.clone().into_iter(), h).expect("Failed to register handler");
                 }
             )*};
         } register! { GET, PUT, POST, PATCH, DELETE }
 
+        for (name, mut h) in extensions {
+            h.param_names = route.param_names().collect();
+            self.extension_mut(name).register_handler(route.clone().into_iter(), h).expect("Failed to register handler");
+        }
+
         self.OPTIONS.register_handler(route.into_iter(), Handler::new(move |req| {
             let mut available_methods = methods.clone();
             if available_methods.contains(&"GET") {
@@ -241,6 +266,9 @@ impl TrieRouter {
                                     .Allow(available_methods.join(", "))
                                 )
                             ```
+
+                            by default. `RadixRouter` turns this into that
+                            when opted in by `Ohkami::answer_options`.
                         */
                         crate::Response::NotFound()
                     }
@@ -257,17 +285,41 @@ impl TrieRouter {
                 )*
             };
         } apply_to! { GET, PUT, POST, PATCH, DELETE, OPTIONS }
+
+        for (_, node) in &mut self.extensions {
+            node.apply_fangs(id.clone(), fangs.clone());
+        }
     }
 
     pub(crate) fn merge_another(&mut self, another: ByAnother) {
         let ByAnother { route, ohkami } = another;
-        let another_routes = ohkami.into_router();
+        if route.ends_with_wildcard() {
+            panic!("Can't mount another Ohkami at wildcard route: `{}`", route.literal())
+        }
+        let mut another_routes = ohkami.into_router();
+        let prefix_param_names = route.param_names().collect::<Vec<_>>();
 
         macro_rules! merge {
             ($( $method:ident ),*) => {$(
+                another_routes.$method.prepend_param_names(&prefix_param_names);
                 self.$method.merge_node(route.clone().into_iter(), another_routes.$method).expect("Can't merge Ohkamis");
             )*};
         } merge! { GET, PUT, POST, PATCH, DELETE, OPTIONS }
+
+        for (name, mut node) in another_routes.extensions {
+            node.prepend_param_names(&prefix_param_names);
+            self.extension_mut(name).merge_node(route.clone().into_iter(), node).expect("Can't merge Ohkamis");
+        }
+    }
+
+    fn extension_mut(&mut self, name: &'static str) -> &mut Node {
+        match self.extensions.iter().position(|(n, _)| *n == name) {
+            Some(i) => &mut self.extensions[i].1,
+            None    => {
+                self.extensions.push((name, Node::root()));
+                &mut self.extensions.last_mut().unwrap().1
+            }
+        }
     }
 
     pub(crate) fn into_radix(self) -> super::RadixRouter {
@@ -278,6 +330,12 @@ impl TrieRouter {
             PATCH:   self.PATCH  .into_radix(),
             DELETE:  self.DELETE .into_radix(),
             OPTIONS: self.OPTIONS.into_radix(),
+            extensions: self.extensions.into_iter()
+                .map(|(name, node)| (name, node.into_radix()))
+                .collect(),
+
+            panic_hook:     self.panic_hook,
+            answer_options: self.answer_options,
         }
     }
 }
@@ -336,6 +394,15 @@ impl Node {
         self.fangs_list.add(id, fangs);
     }
 
+    fn prepend_param_names(&mut self, prefix: &[&'static str]) {
+        if let Some(handler) = &mut self.handler {
+            handler.param_names.splice(0..0, prefix.iter().copied());
+        }
+        for child in &mut self.children {
+            child.prepend_param_names(prefix)
+        }
+    }
+
     #[allow(unused_mut)]
     fn into_radix(self) -> super::radix::Node {
         let Node { pattern, mut fangs_list, mut handler, mut children } = self;
@@ -359,7 +426,7 @@ impl Node {
             fangs_list.extend(child_fangses);
             
             let child_pattern = child_pattern.unwrap(/* `child` is not root */);
-            if patterns.last().is_some_and(|last| !last.is_param()) && (!child_pattern.is_param()) {
+            if patterns.last().is_some_and(|last| last.to_static().is_some()) && child_pattern.to_static().is_some() {
                 let last_pattern = patterns.pop(/*=== POPing here ===*/).unwrap();
                 let this_static  = last_pattern.to_static().unwrap();
                 let child_static = child_pattern.to_static().unwrap();
@@ -372,18 +439,28 @@ impl Node {
             }
         }
 
+        /* static ones first, then param, and wildcard last as the fallback */
         children.sort_unstable_by(|a, b| match (a.pattern.as_ref().unwrap(), b.pattern.as_ref().unwrap()) {
-            (Pattern::Static(_), Pattern::Param)     => std::cmp::Ordering::Less,
-            (Pattern::Param, Pattern::Static(_))     => std::cmp::Ordering::Greater,
             (Pattern::Static(a), Pattern::Static(b)) => <[u8]>::cmp(&a, &b).reverse(),
-            _ => std::cmp::Ordering::Equal
+            (a, b) => a.priority().cmp(&b.priority()),
         });
 
+        let param_names = match &handler {
+            None          => &[][..],
+            Some(handler) => {
+                if let Err(e) = handler.validate_params() {
+                    panic!("{e}")
+                }
+                handler.param_names.clone().leak()
+            }
+        };
+
         super::radix::Node {
-            patterns:  Box::leak(patterns.into_iter().map(Pattern::into_radix).collect()),
-            children:  Box::leak(children.into_iter().map(Node::into_radix).collect::<Box<[_]>>()),
-            proc:      fangs_list.clone().into_proc_with(handler.unwrap_or(Handler::default_not_found())),
-            __catch__: fangs_list.into_proc_with(Handler::default_not_found()),
+            patterns:    Box::leak(patterns.into_iter().map(Pattern::into_radix).collect()),
+            children:    Box::leak(children.into_iter().map(Node::into_radix).collect::<Box<[_]>>()),
+            param_names,
+            proc:        handler.map(|handler| fangs_list.clone().into_proc_with(handler)),
+            __catch__:   fangs_list.into_proc_with(Handler::default_not_found()),
         }
     }
 }
@@ -483,7 +560,7 @@ impl Node {
 impl Node {
     fn append_child(&mut self, new_child: Node) -> Result<(), String> {
         match new_child.pattern.as_ref().expect("Invalid child node: Child node must have pattern") {
-            Pattern::Param => {
+            Pattern::Param | Pattern::Wildcard => {
                 self.children.push(new_child);
                 Ok(())
             }
@@ -514,30 +591,29 @@ impl Node {
 }
 
 impl Pattern {
-    fn is_param(&self) -> bool {
+    fn priority(&self) -> u8 {
         match self {
-            Self::Param => true,
-            Self::Static(_) => false,
+            Self::Static(_) => 0,
+            Self::Param     => 1,
+            Self::Wildcard  => 2,
         }
     }
 
     fn to_static(&self) -> Option<&[u8]> {
         match self {
-            Self::Param         => None,
-            Self::Static(bytes) => Some(&bytes)
+            Self::Param | Self::Wildcard => None,
+            Self::Static(bytes)          => Some(&bytes)
         }
     }
 
     fn matches(&self, another: &Self) -> bool {
-        match self {
-            Self::Param => another.is_param(),
-            Self::Static{..} => self.to_static() == another.to_static(),
-        }
+        self == another
     }
 
     fn into_radix(self) -> super::radix::Pattern {
         match self {
             Self::Param                        => super::radix::Pattern::Param,
+            Self::Wildcard                     => super::radix::Pattern::Wildcard,
             Self::Static(Cow::Borrowed(bytes)) => super::radix::Pattern::Static(bytes),
             Self::Static(Cow::Owned(vec))      => super::radix::Pattern::Static(vec.leak()),
         }
@@ -1,7 +1,15 @@
 #![cfg(feature="__rt_native__")]
 
 #[allow(unused)]
-use super::{Request, Method, BUF_SIZE, Path, QueryParams, Store};
+use super::{Request, Method, Path, QueryParams, Store, Disconnected};
+
+/// Status of the response to an invalid request, not expecting I/O errors
+fn status(err: super::ReadError) -> crate::Status {
+    match err {
+        super::ReadError::Respond(res) => res.status,
+        super::ReadError::Io(e)        => panic!("Unexpected I/O error: {e}"),
+    }
+}
 
 #[test]
 fn parse_path() {
@@ -24,18 +32,19 @@ fn parse_path() {
     use std::pin::Pin;
     use ohkami_lib::{Slice, CowSlice};
 
-    fn metadataize(input: &str) -> Box<[u8; BUF_SIZE]> {
-        let mut buf = [0; BUF_SIZE];
-        buf[..input.len().min(BUF_SIZE)]
-            .copy_from_slice(&input.as_bytes()[..input.len().min(BUF_SIZE)]);
-        Box::new(buf)
+    fn metadataize(input: &str) -> Box<[u8]> {
+        let buf_size = crate::ServerConfig::default().header_buffer_size;
+        let mut buf = vec![0; buf_size];
+        buf[..input.len().min(buf_size)]
+            .copy_from_slice(&input.as_bytes()[..input.len().min(buf_size)]);
+        buf.into_boxed_slice()
     }
 
     macro_rules! assert_parse {
         ($case:expr, $expected:expr) => {
-            let mut actual = Request::init(crate::util::IP_0000);
+            let mut actual = Request::init(crate::util::PEER_0000);
             let mut actual = unsafe {Pin::new_unchecked(&mut actual)};
-            actual.as_mut().read(&mut $case.as_bytes()).await.ok();
+            actual.as_mut().read(&mut $case.as_bytes(), &crate::ServerConfig::default()).await.ok();
 
             let expected = $expected;
 
@@ -68,7 +77,13 @@ fn parse_path() {
     const _CASE_1_LEN: usize = CASE_1.len();
     assert_parse!(CASE_1, Request {
         __buf__: metadataize(CASE_1),
+        __parser__: super::parse::Parser::new(),
+        __rest__: 0..0,
+        __spilled__: Vec::new(),
+        #[cfg(feature="http2")]
+        __head__: None,
         method:  Method::GET,
+        extension_method: None,
         path:    Path::from_literal("/hello.html"),
         query:   None,
         headers: RequestHeaders::from_iters([
@@ -80,7 +95,12 @@ fn parse_path() {
         ], None),
         payload: None,
         store:   Store::init(),
-        ip:      crate::util::IP_0000
+        ip:      crate::util::IP_0000,
+        peer:    crate::util::PEER_0000,
+        proxy:   None,
+        disconnected: Disconnected(None),
+        #[cfg(feature="tls")]
+        tls:     None,
     });
 
 
@@ -97,7 +117,13 @@ fn parse_path() {
     const _CASE_2_LEN: usize = CASE_2.len();
     assert_parse!(CASE_2, Request {
         __buf__: metadataize(CASE_2),
+        __parser__: super::parse::Parser::new(),
+        __rest__: 0..0,
+        __spilled__: Vec::new(),
+        #[cfg(feature="http2")]
+        __head__: None,
         method:  Method::POST,
+        extension_method: None,
         path:    Path::from_literal("/signup"),
         query:   None,
         headers: RequestHeaders::from_iters([
@@ -111,7 +137,12 @@ fn parse_path() {
             br#"{"name":"kanarus","age":20}"#
         ))),
         store: Store::init(),
-        ip:    crate::util::IP_0000
+        ip:    crate::util::IP_0000,
+        peer:  crate::util::PEER_0000,
+        proxy: None,
+        disconnected: Disconnected(None),
+        #[cfg(feature="tls")]
+        tls:   None,
     });
 
     {
@@ -133,7 +164,13 @@ fn parse_path() {
         const _CASE_3_LEN: usize = CASE_3.len();
         assert_parse!(CASE_3, Request {
             __buf__: metadataize(CASE_3),
+            __parser__: super::parse::Parser::new(),
+            __rest__: 0..0,
+            __spilled__: Vec::new(),
+            #[cfg(feature="http2")]
+            __head__: None,
             method:  Method::POST,
+            extension_method: None,
             path:    Path::from_literal("/foo.php"),
             query:   Some(QueryParams::from([
                 ("query", "1"),
@@ -157,7 +194,555 @@ fn parse_path() {
             ),
             payload: Some(CowSlice::Own(Vec::from("first_name=John&last_name=Doe&action=Submit").into())),
             store:   Store::init(),
-            ip:      crate::util::IP_0000
+            ip:      crate::util::IP_0000,
+            peer:    crate::util::PEER_0000,
+            proxy:   None,
+            disconnected: Disconnected(None),
+            #[cfg(feature="tls")]
+            tls:     None,
         });
     }
 }
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_parse_with_config() {
+    use crate::{ServerConfig, Status};
+    use std::pin::Pin;
+
+    async fn parse(input: &str, config: &ServerConfig) -> Result<Option<()>, Status> {
+        let mut req = Request::init(crate::util::PEER_0000);
+        let mut req = unsafe {Pin::new_unchecked(&mut req)};
+        req.as_mut().read(&mut input.as_bytes(), config).await
+            .map_err(status)
+    }
+
+    const THREE_HEADERS: &str = "\
+        GET / HTTP/1.1\r\n\
+        Host: localhost\r\n\
+        Accept: */*\r\n\
+        X-Request-Id: 300\r\n\
+        \r\n\
+    ";
+    assert_eq!(parse(THREE_HEADERS, &ServerConfig::new().max_headers(3)).await, Ok(Some(())));
+    assert_eq!(parse(THREE_HEADERS, &ServerConfig::new().max_headers(2)).await, Err(Status::RequestHeaderFieldsTooLarge));
+
+    const PAYLOAD_5: &str = "\
+        POST / HTTP/1.1\r\n\
+        Content-Length: 5\r\n\
+        \r\n\
+        hello\
+    ";
+    assert_eq!(parse(PAYLOAD_5, &ServerConfig::new().payload_limit(5)).await, Ok(Some(())));
+    assert_eq!(parse(PAYLOAD_5, &ServerConfig::new().payload_limit(4)).await, Err(Status::PayloadTooLarge));
+
+    /* `THREE_HEADERS` is 67 bytes: grown 16 -> 32 -> 64 -> 128 */
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut THREE_HEADERS.as_bytes(), &ServerConfig::new().header_buffer_size(16)).await;
+    assert!(matches!(result, Ok(Some(()))));
+    assert_eq!(req.__buf__.len(), 128);
+    assert_eq!(req.headers.custom("X-Request-Id"), Some("300"));
+
+    assert_eq!(parse(THREE_HEADERS, &ServerConfig::new().header_buffer_size(16).max_header_size(67)).await, Ok(Some(())));
+    assert_eq!(parse(THREE_HEADERS, &ServerConfig::new().header_buffer_size(16).max_header_size(66)).await, Err(Status::RequestHeaderFieldsTooLarge));
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_parse_large_headers() {
+    use crate::ServerConfig;
+    use std::pin::Pin;
+
+    let token = "x".repeat(4000);
+    let input = format!("\
+        GET /api HTTP/1.1\r\n\
+        Host: localhost\r\n\
+        Authorization: Bearer {token}\r\n\
+        X-Trace-Id: 42\r\n\
+        \r\n\
+    ");
+
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut input.as_bytes(), &ServerConfig::default()).await;
+    assert!(matches!(result, Ok(Some(()))));
+    assert_eq!(req.headers.Authorization(), Some(&*format!("Bearer {token}")));
+    assert_eq!(req.headers.custom("X-Trace-Id"), Some("42"));
+
+    /* buffer is kept for the next request on the same connection */
+    let grown = req.__buf__.len();
+    req.clear();
+    let result = req.as_mut().read(&mut "GET / HTTP/1.1\r\n\r\n".as_bytes(), &ServerConfig::default()).await;
+    assert!(matches!(result, Ok(Some(()))));
+    assert_eq!(req.__buf__.len(), grown);
+}
+
+#[test] fn test_parser_byte_by_byte() {
+    use super::parse::{Parser, Status};
+
+    const HEAD: &[u8] = b"\
+        POST /api/users?page=2 HTTP/1.1\r\n\
+        Host: localhost\r\n\
+        Content-Type:application/json\r\n\
+        X-Empty: \r\n\
+        Content-Length: 2\r\n\
+        \r\n\
+    ";
+    let input = [HEAD, b"{}"].concat();
+
+    /* every split point gives the same result */
+    for split in 0..=input.len() {
+        let mut parser = Parser::new();
+
+        let first = parser.parse(&input[..split], 100, true).unwrap();
+        if split < HEAD.len() {
+            assert_eq!(first, Status::Partial, "split at {split}");
+        }
+        assert_eq!(parser.parse(&input, 100, true), Ok(Status::Complete(HEAD.len())), "split at {split}");
+
+        assert_eq!(&input[parser.method()], b"POST");
+        assert_eq!(&input[parser.path()], b"/api/users");
+        assert_eq!(parser.query().map(|q| &input[q]), Some(&b"page=2"[..]));
+        assert_eq!(
+            parser.headers().map(|(n, v)| (&input[n], &input[v])).collect::<Vec<_>>(),
+            [
+                (&b"Host"[..],         &b"localhost"[..]),
+                (&b"Content-Type"[..], &b"application/json"[..]),
+                (&b"X-Empty"[..],      &b""[..]),
+                (&b"Content-Length"[..], &b"2"[..]),
+            ]
+        );
+    }
+
+    /* fed one byte at a time */
+    let mut parser = Parser::new();
+    for len in 1..HEAD.len() {
+        assert_eq!(parser.parse(&input[..len], 100, true), Ok(Status::Partial), "at {len}");
+    }
+    assert_eq!(parser.parse(&input[..HEAD.len()], 100, true), Ok(Status::Complete(HEAD.len())));
+}
+
+#[test] fn test_parser_errors() {
+    use super::parse::{Parser, Status, Error};
+
+    fn parse(input: &str) -> Result<Status, Error> {
+        Parser::new().parse(input.as_bytes(), 2, true)
+    }
+
+    assert_eq!(parse("\r\nGET / HTTP/1.1\r\n\r\n"), Ok(Status::Complete(20)));
+    assert_eq!(parse("GET / HTTP/1.1\r\nHost: a\r\nAccept: */*\r\n\r\n"), Ok(Status::Complete(40)));
+    assert_eq!(parse("GET / HTTP/1.1\r\nHost: a"), Ok(Status::Partial));
+
+    assert_eq!(parse("GOT / HTTP/1.1\r\n\r\n"),    Ok(Status::Complete(18)));
+    assert_eq!(parse(" / HTTP/1.1\r\n\r\n"),       Err(Error::UnknownMethod));
+    assert_eq!(parse("\x16\x03\x01\x02\x00\x01"),  Err(Error::UnknownMethod));
+    assert_eq!(parse("GET / HTTP/2.0\r\n\r\n"),    Err(Error::VersionNotSupported));
+    assert_eq!(parse("GET /\r\n\r\n"),             Err(Error::BadRequest));
+    assert_eq!(parse("GET / HTTP/1.1\n\r\n"),      Err(Error::BadRequest));
+    assert_eq!(parse("GET / HTTP/1.1\r\nHost: a\n\r\n"), Err(Error::BadRequest));
+    assert_eq!(parse("GET / HTTP/1.1\r\n: a\r\n\r\n"),   Err(Error::BadRequest));
+    assert_eq!(parse("GET / HTTP/1.1\r\nHost\r\n\r\n"),  Err(Error::BadRequest));
+    assert_eq!(parse("GET / HTTP/1.1\r\nA: 1\r\nB: 2\r\nC: 3\r\n\r\n"), Err(Error::TooManyHeaders));
+}
+
+#[cfg(feature="rt_tokio")]
+#[crate::__rt__::test] async fn test_read_request_fragmented() {
+    use crate::ServerConfig;
+    use std::{pin::Pin, task::{Context, Poll}};
+    use tokio::io::{AsyncRead, ReadBuf};
+
+    /// yields at most one byte per `read`
+    struct OneByOne<'b>(&'b [u8]);
+    impl AsyncRead for OneByOne<'_> {
+        fn poll_read(mut self: Pin<&mut Self>, _: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<std::io::Result<()>> {
+            if let Some((first, rest)) = self.0.split_first() {
+                buf.put_slice(&[*first]);
+                self.0 = rest;
+            }
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    let input = "\
+        PUT /users/42?verbose=true HTTP/1.1\r\n\
+        Host: localhost\r\n\
+        Content-Length: 13\r\n\
+        \r\n\
+        {\"name\":\"a\"}\n\
+    ";
+
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut OneByOne(input.as_bytes()), &ServerConfig::new().header_buffer_size(8)).await;
+    assert!(matches!(result, Ok(Some(()))));
+    assert!(req.method.isPUT());
+    assert_eq!(&*req.path, "/users/42");
+    assert_eq!(req.headers.Host(), Some("localhost"));
+    assert_eq!(req.payload.as_deref(), Some(&b"{\"name\":\"a\"}\n"[..]));
+}
+
+#[test] fn test_chunked_decoder() {
+    use super::parse::{ChunkedDecoder, Status, Error};
+
+    const BODY: &[u8] = b"\
+        5\r\nHello\r\n\
+        7;name=value\r\n, chunk\r\n\
+        9 \r\ned world!\r\n\
+        0\r\n\
+        Expires: never\r\n\
+        \r\n\
+    ";
+    let input = [BODY, b"GET / HTTP/1.1\r\n\r\n"].concat();
+
+    /* all at once */
+    let mut payload = Vec::new();
+    assert_eq!(ChunkedDecoder::new().decode(&input, &mut payload, 1 << 10), Ok(Status::Complete(BODY.len())));
+    assert_eq!(payload, b"Hello, chunked world!");
+
+    /* one byte at a time */
+    let mut decoder = ChunkedDecoder::new();
+    let mut payload = Vec::new();
+    for i in 0..BODY.len()-1 {
+        assert_eq!(decoder.decode(&BODY[i..i+1], &mut payload, 1 << 10), Ok(Status::Partial), "at {i}");
+    }
+    assert_eq!(decoder.decode(&input[BODY.len()-1..], &mut payload, 1 << 10), Ok(Status::Complete(1)));
+    assert_eq!(payload, b"Hello, chunked world!");
+
+    fn decode(input: &str, limit: usize) -> Result<Status, Error> {
+        ChunkedDecoder::new().decode(input.as_bytes(), &mut Vec::new(), limit)
+    }
+
+    assert_eq!(decode("0\r\n\r\n", 0), Ok(Status::Complete(5)));
+    assert_eq!(decode("5\r\nHello\r\n0\r\n\r\n", 5), Ok(Status::Complete(15)));
+    assert_eq!(decode("5\r\nHello\r\n0\r\n\r\n", 4), Err(Error::PayloadTooLarge));
+    assert_eq!(decode("0\r\nX-Long-Trailer: abcdefg\r\n\r\n", 8), Err(Error::PayloadTooLarge));
+    assert_eq!(decode("fffffffffffffffffffffffff\r\n", usize::MAX), Err(Error::PayloadTooLarge));
+
+    assert_eq!(decode("\r\n", 1 << 10), Err(Error::BadRequest));
+    assert_eq!(decode("-1\r\n", 1 << 10), Err(Error::BadRequest));
+    assert_eq!(decode("5\nHello\r\n", 1 << 10), Err(Error::BadRequest));
+    assert_eq!(decode("5\r\nHello world\r\n", 1 << 10), Err(Error::BadRequest));
+    assert_eq!(decode("0\r\nExpires: never\n\r\n", 1 << 10), Err(Error::BadRequest));
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_read_chunked_request() {
+    use crate::{ServerConfig, Status};
+    use std::pin::Pin;
+
+    let input = "\
+        POST /upload HTTP/1.1\r\n\
+        Transfer-Encoding: chunked\r\n\
+        \r\n\
+        5\r\nHello\r\n\
+        8\r\n, world!\r\n\
+        0\r\n\
+        \r\n\
+    ";
+
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut input.as_bytes(), &ServerConfig::default()).await;
+    assert!(matches!(result, Ok(Some(()))));
+    assert_eq!(req.payload.as_deref(), Some(&b"Hello, world!"[..]));
+
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut input.as_bytes(), &ServerConfig::new().payload_limit(12)).await;
+    assert_eq!(result.err().map(status), Some(Status::PayloadTooLarge));
+
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut input.replace("chunked", "gzip").as_bytes(), &ServerConfig::default()).await;
+    assert_eq!(result.err().map(status), Some(Status::BadRequest));
+
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let result = req.as_mut().read(&mut input.replace("chunked", "gzip").as_bytes(), &ServerConfig::new().strict_parsing(false)).await;
+    assert_eq!(result.err().map(status), Some(Status::NotImplemented));
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_request_smuggling_corpus() {
+    use crate::{ServerConfig, Status};
+    use std::pin::Pin;
+
+    async fn parse(input: &str, config: &ServerConfig) -> Result<Option<Vec<u8>>, Status> {
+        let mut req = Request::init(crate::util::PEER_0000);
+        let mut req = unsafe {Pin::new_unchecked(&mut req)};
+        match req.as_mut().read(&mut input.as_bytes(), config).await {
+            Ok(Some(())) => Ok(Some(req.payload.as_deref().unwrap_or_default().to_vec())),
+            Ok(None)     => Ok(None),
+            Err(res)     => Err(status(res)),
+        }
+    }
+
+    for (case, expected) in [
+        /* Content-Length */
+        ("POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 6\r\n\r\nhello!",                    Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nhello",                     Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 5, 5\r\n\r\nhello",                                        Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: +5\r\n\r\nhello",                                          Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: -5\r\n\r\nhello",                                          Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 5a\r\n\r\nhello",                                          Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 0x5\r\n\r\nhello",                                         Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 5 5\r\n\r\nhello",                                         Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: \r\n\r\nhello",                                            Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 99999999999999999999999\r\n\r\nhello",                     Status::PayloadTooLarge),
+
+        /* Transfer-Encoding */
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 3\r\n\r\n0\r\n\r\n",          Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 3\r\nTRANSFER-ENCODING: chunked\r\n\r\n0\r\n\r\n",          Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 3\r\ntransfer-encoding: chunked\r\n\r\n0\r\n\r\n",          Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: xchunked\r\n\r\n0\r\n\r\n",                             Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked, identity\r\n\r\n0\r\n\r\n",                    Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked, chunked\r\n\r\n0\r\n\r\n",                     Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n", Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: gzip, chunked\r\n\r\n0\r\n\r\n",                        Status::NotImplemented),
+
+        /* header syntax */
+        ("POST / HTTP/1.1\r\nTransfer-Encoding : chunked\r\n\r\n0\r\n\r\n",                             Status::BadRequest),
+        ("POST / HTTP/1.1\r\nX-Padding: a\r\n Transfer-Encoding: chunked\r\n\r\n0\r\n\r\n",              Status::BadRequest),
+        ("POST / HTTP/1.1\r\n Transfer-Encoding: chunked\r\n\r\n0\r\n\r\n",                             Status::BadRequest),
+        ("POST / HTTP/1.1\r\nX-Padding: a\r\n\tb\r\n\r\n",                                              Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 0\nX-Smuggled: 1\r\n\r\n",                                Status::BadRequest),
+        ("POST / HTTP/1.1\r\nContent-Length: 0\rX-Smuggled: 1\r\n\r\n",                                Status::BadRequest),
+        ("POST / HTTP/1.1\nContent-Length: 0\r\n\r\n",                                                  Status::BadRequest),
+        ("POST / HTTP/1.1\r\nX-Value: a\x00b\r\n\r\n",                                                  Status::BadRequest),
+        ("POST / HTTP/1.1\r\nX-Value: a\x7Fb\r\n\r\n",                                                  Status::BadRequest),
+        ("POST /a\x00b HTTP/1.1\r\n\r\n",                                                               Status::BadRequest),
+        ("POST /?q=\x01 HTTP/1.1\r\n\r\n",                                                              Status::BadRequest),
+        ("POST / HTTP/1.1\r\nX(Value): a\r\n\r\n",                                                      Status::BadRequest),
+
+        /* chunk size */
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n-5\r\nhello\r\n0\r\n\r\n",                Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n0x5\r\nhello\r\n0\r\n\r\n",               Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\nhello\r\n0\r\n\r\n",                   Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n3\r\nhello\r\n0\r\n\r\n",                 Status::BadRequest),
+        ("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\nffffffffffffffff\r\nhello\r\n0\r\n\r\n", Status::PayloadTooLarge),
+
+        /* Host */
+        ("GET / HTTP/1.1\r\nHost: a.example\r\nHost: b.example\r\n\r\n",                                Status::BadRequest),
+        ("GET / HTTP/1.1\r\nHost: a.example, b.example\r\n\r\n",                                        Status::BadRequest),
+    ] {
+        assert_eq!(parse(case, &ServerConfig::default()).await, Err(expected), "{case:?}");
+    }
+
+    /* `strict_parsing(false)` accepts some of legacy clients' requests... */
+    let lenient = ServerConfig::new().strict_parsing(false);
+    assert_eq!(parse("POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 5\r\n\r\nhello", &lenient).await,
+        Ok(Some(b"hello".to_vec())));
+    assert_eq!(parse("POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\nContent-Length: 3\r\n\r\n5\r\nhello\r\n0\r\n\r\n", &lenient).await,
+        Ok(Some(b"hello".to_vec())));
+    assert_eq!(parse("GET / HTTP/1.1\r\nX-Value: a\x01b\r\n\r\n", &lenient).await,
+        Ok(Some(vec![])));
+
+    /* ...but never the unresolvable ones */
+    for case in [
+        "POST / HTTP/1.1\r\nContent-Length: 5\r\nContent-Length: 6\r\n\r\nhello!",
+        "POST / HTTP/1.1\r\nContent-Length: +5\r\n\r\nhello",
+        "POST / HTTP/1.1\r\nContent-Length: 0\nX-Smuggled: 1\r\n\r\n",
+    ] {
+        assert_eq!(parse(case, &lenient).await, Err(Status::BadRequest), "{case:?}");
+    }
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_read_pipelined_requests() {
+    use crate::ServerConfig;
+    use std::pin::Pin;
+
+    let input = "\
+        POST /a HTTP/1.1\r\n\
+        Content-Length: 5\r\n\
+        \r\n\
+        hello\
+        POST /b HTTP/1.1\r\n\
+        Transfer-Encoding: chunked\r\n\
+        \r\n\
+        5\r\nworld\r\n\
+        0\r\n\
+        \r\n\
+        GET /c HTTP/1.1\r\n\
+        \r\n\
+        GET /d HTT\
+    ";
+
+    for config in [
+        ServerConfig::default(),
+        /* the chunked body of `/b` is read beyond the buffer */
+        ServerConfig::new().header_buffer_size(48),
+    ] {
+        let mut stream = input.as_bytes();
+        let mut req = Request::init(crate::util::PEER_0000);
+        let mut req = unsafe {Pin::new_unchecked(&mut req)};
+
+        for (path, payload) in [("/a", Some(&b"hello"[..])), ("/b", Some(&b"world"[..])), ("/c", None)] {
+            req.clear();
+            let result = req.as_mut().read(&mut stream, &config).await;
+            assert!(matches!(result, Ok(Some(()))), "{path}");
+            assert_eq!(&*req.path, path);
+            assert_eq!(req.payload.as_deref(), payload, "{path}");
+        }
+
+        /* incomplete one is waited for the rest */
+        req.clear();
+        let result = req.as_mut().read(&mut &b"P/1.1\r\n\r\n"[..], &config).await;
+        assert!(matches!(result, Ok(Some(()))));
+        assert_eq!(&*req.path, "/d");
+    }
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_read_http10_and_absolute_form() {
+    use crate::{ServerConfig, Status};
+    use std::pin::Pin;
+
+    async fn read(input: &str) -> Result<Request, Status> {
+        let mut req = Request::init(crate::util::PEER_0000);
+        let mut pinned = unsafe {Pin::new_unchecked(&mut req)};
+        match pinned.as_mut().read(&mut input.as_bytes(), &ServerConfig::default()).await {
+            Ok(Some(())) => Ok(req),
+            Ok(None)     => panic!("unexpected EOF: {input:?}"),
+            Err(res)     => Err(status(res)),
+        }
+    }
+
+    let req = read("GET /health HTTP/1.0\r\n\r\n").await.unwrap();
+    assert!(req.is_http10());
+    assert_eq!(&*req.path, "/health");
+    let req = read("GET /health HTTP/1.1\r\n\r\n").await.unwrap();
+    assert!(!req.is_http10());
+
+    let req = read("GET http://example.com:8080/users/42?verbose=true HTTP/1.1\r\nHost: ignored.example\r\n\r\n").await.unwrap();
+    assert_eq!(&*req.path, "/users/42");
+    assert!(req.query == Some(QueryParams::new(b"verbose=true")));
+    assert_eq!(req.headers.Host(), Some("example.com:8080"));
+
+    let req = read("GET HTTPS://example.com HTTP/1.0\r\n\r\n").await.unwrap();
+    assert_eq!(&*req.path, "/");
+    assert_eq!(req.headers.Host(), Some("example.com"));
+    let req = read("GET http://example.com?q=1 HTTP/1.1\r\n\r\n").await.unwrap();
+    assert_eq!(&*req.path, "/");
+    assert!(req.query == Some(QueryParams::new(b"q=1")));
+
+    for (case, expected) in [
+        ("GET / HTTP/0.9\r\n\r\n",                         Status::HTTPVersionNotSupported),
+        ("GET / HTTP/2.0\r\n\r\n",                         Status::HTTPVersionNotSupported),
+        ("GET ftp://example.com/ HTTP/1.1\r\n\r\n",        Status::BadRequest),
+        ("GET http:/example.com/ HTTP/1.1\r\n\r\n",        Status::BadRequest),
+        ("GET http:///path HTTP/1.1\r\n\r\n",              Status::BadRequest),
+        ("GET http://user@example.com/ HTTP/1.1\r\n\r\n",  Status::BadRequest),
+        ("POST / HTTP/1.0\r\nTransfer-Encoding: chunked\r\n\r\n0\r\n\r\n", Status::BadRequest),
+    ] {
+        assert_eq!(read(case).await.err(), Some(expected), "{case:?}");
+    }
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_read_truncated_body() {
+    use super::ReadError;
+    use crate::ServerConfig;
+    use std::pin::Pin;
+
+    for input in [
+        "POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\nabc",
+        "POST / HTTP/1.1\r\nContent-Length: 10\r\n\r\n",
+    ] {
+        let mut req = Request::init(crate::util::PEER_0000);
+        let mut req = unsafe {Pin::new_unchecked(&mut req)};
+        let result = req.as_mut().read(&mut input.as_bytes(), &ServerConfig::default()).await;
+        assert!(matches!(&result, Err(ReadError::Io(e)) if e.kind() == std::io::ErrorKind::UnexpectedEof), "{input:?}: {result:?}");
+    }
+}
+
+#[cfg(any(feature="rt_tokio",feature="rt_async-std"))]
+#[crate::__rt__::test] async fn test_read_body_at_min_rate() {
+    use super::ReadError;
+    use crate::{ServerConfig, Status};
+    use std::{io, pin::Pin, task::{Context, Poll}, time::Duration, future::Future};
+
+    /// In-memory stream giving `head` at once, then `body` by `chunk` bytes every `interval`
+    struct Throttled {
+        head:     &'static [u8],
+        body:     &'static [u8],
+        chunk:    usize,
+        interval: Duration,
+        sleep:    Option<Pin<Box<dyn Future<Output = ()>>>>,
+    }
+    impl Throttled {
+        fn new(head: &'static str, body: &'static str, chunk: usize, interval: Duration) -> Self {
+            Self { head: head.as_bytes(), body: body.as_bytes(), chunk, interval, sleep: None }
+        }
+        fn poll_next(&mut self, cx: &mut Context<'_>, max: usize) -> Poll<&'static [u8]> {
+            if !self.head.is_empty() {
+                return Poll::Ready(std::mem::take(&mut self.head))
+            }
+            if self.body.is_empty() {
+                return Poll::Ready(&[])
+            }
+            let sleep = self.sleep.get_or_insert_with(|| Box::pin(crate::__rt__::sleep(self.interval)));
+            if sleep.as_mut().poll(cx).is_pending() {
+                return Poll::Pending
+            }
+            self.sleep = None;
+            let (next, rest) = self.body.split_at(self.chunk.min(max).min(self.body.len()));
+            self.body = rest;
+            Poll::Ready(next)
+        }
+    }
+
+    #[cfg(feature="rt_tokio")]
+    impl tokio::io::AsyncRead for Throttled {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut tokio::io::ReadBuf<'_>) -> Poll<io::Result<()>> {
+            self.get_mut().poll_next(cx, buf.remaining()).map(|next| {
+                buf.put_slice(next);
+                Ok(())
+            })
+        }
+    }
+    #[cfg(feature="rt_async-std")]
+    impl futures_util::io::AsyncRead for Throttled {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            self.get_mut().poll_next(cx, buf.len()).map(|next| {
+                buf[..next.len()].copy_from_slice(next);
+                Ok(next.len())
+            })
+        }
+    }
+
+    let config = ServerConfig::new().min_body_rate(100);
+
+    /* 200 bytes/sec */
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let mut stream = Throttled::new(
+        "POST / HTTP/1.1\r\nContent-Length: 300\r\n\r\n",
+        Box::leak("x".repeat(300).into_boxed_str()),
+        20, Duration::from_millis(100),
+    );
+    let result = req.as_mut().read(&mut stream, &config).await;
+    assert!(matches!(result, Ok(Some(()))), "{result:?}");
+    assert_eq!(req.payload().map(<[u8]>::len), Some(300));
+
+    /* 50 bytes/sec */
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let mut stream = Throttled::new(
+        "POST / HTTP/1.1\r\nContent-Length: 300\r\n\r\n",
+        Box::leak("x".repeat(300).into_boxed_str()),
+        5, Duration::from_millis(100),
+    );
+    let result = req.as_mut().read(&mut stream, &config).await;
+    assert!(matches!(&result, Err(ReadError::Respond(res)) if res.status == Status::RequestTimeout), "{result:?}");
+
+    /* chunked, 50 bytes/sec */
+    let mut req = Request::init(crate::util::PEER_0000);
+    let mut req = unsafe {Pin::new_unchecked(&mut req)};
+    let mut stream = Throttled::new(
+        "POST / HTTP/1.1\r\nTransfer-Encoding: chunked\r\n\r\n",
+        Box::leak(format!("12c\r\n{}\r\n0\r\n\r\n", "x".repeat(300)).into_boxed_str()),
+        5, Duration::from_millis(100),
+    );
+    let result = req.as_mut().read(&mut stream, &config).await;
+    assert!(matches!(&result, Err(ReadError::Respond(res)) if res.status == Status::RequestTimeout), "{result:?}");
+}
@@ -39,6 +39,12 @@ pub trait FromRequest<'req>: Sized {
     
     fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>>;
 
+    /// Names of path params this extractor requires,
+    /// checked against the route at registration
+    #[doc(hidden)]
+    fn required_path_params() -> Option<&'static [&'static str]> {
+        None
+    }
 }
 const _: () = {
     impl<'req> FromRequest<'req> for &'req Request {
@@ -139,6 +139,17 @@ macro_rules! Header {
                     _ => None
                 }
             }
+            /// Fallback of `from_bytes` for unusual cases like `CONTENT-LENGTH`
+            #[cfg(any(feature="__rt_native__", all(feature="rt_worker", feature="testing")))]
+            #[cold]
+            pub(crate) fn from_bytes_ignore_ascii_case(bytes: &[u8]) -> Option<Self> {
+                $(
+                    if bytes.eq_ignore_ascii_case($name_bytes) {
+                        return Some(Self::$konst)
+                    }
+                )*
+                None
+            }
         }
 
         impl<T: AsRef<[u8]>> PartialEq<T> for Header {
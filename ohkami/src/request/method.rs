@@ -1,4 +1,5 @@
 #[derive(Clone, Copy, PartialEq)]
+#[non_exhaustive]
 pub enum Method {
     GET,
     PUT,
@@ -7,6 +8,20 @@ pub enum Method {
     DELETE,
     HEAD,
     OPTIONS,
+    /// An extension method like WebDAV's `PROPFIND` or `QUERY`,
+    /// routed by `.method(name, handler)`. Requests of the other
+    /// methods are answered `501 Not Implemented`.
+    Extension(&'static str),
+}
+
+/// `tchar` of RFC 9110, 5.6.2
+#[cfg(feature="__rt__")]
+#[inline(always)]
+pub(crate) const fn is_tchar(b: u8) -> bool {
+    matches!(b,
+        b'!' | b'#' | b'$' | b'%' | b'&' | b'\'' | b'*' | b'+' | b'-' | b'.' |
+        b'^' | b'_' | b'`' | b'|' | b'~' | b'0'..=b'9' | b'A'..=b'Z' | b'a'..=b'z'
+    )
 }
 
 impl Method {
@@ -22,6 +37,18 @@ impl Method {
             _ => None
         }
     }
+    /// Method named `name`, an extension method if it's not a standard one
+    #[cfg(feature="__rt__")]
+    pub(crate) fn named(name: &'static str) -> Result<Self, String> {
+        if let Some(method) = Self::from_bytes(name.as_bytes()) {
+            return Ok(method)
+        }
+        if name.is_empty() || !name.bytes().all(is_tchar) {
+            return Err(format!("Invalid method name: `{name}`"))
+        }
+        Ok(Self::Extension(name))
+    }
+
     #[cfg(feature="rt_worker")]
     #[inline(always)] pub(crate) const fn from_worker(w: ::worker::Method) -> Option<Self> {
         match w {
@@ -45,6 +72,7 @@ impl Method {
             Self::DELETE  => "DELETE",
             Self::HEAD    => "HEAD",
             Self::OPTIONS => "OPTIONS",
+            Self::Extension(name) => name,
         }
     }
 }
@@ -75,29 +103,13 @@ impl Method {
 const _: () = {
     impl std::fmt::Display for Method {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.write_str(match self {
-                Self::GET     => "GET",
-                Self::PUT     => "PUT",
-                Self::POST    => "POST",
-                Self::PATCH   => "PATCH",
-                Self::DELETE  => "DELETE",
-                Self::HEAD    => "HEAD",
-                Self::OPTIONS => "OPTIONS",
-            })
+            f.write_str(self.as_str())
         }
     }
     
     impl std::fmt::Debug for Method {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-            f.write_str(match self {
-                Self::GET     => "GET",
-                Self::PUT     => "PUT",
-                Self::POST    => "POST",
-                Self::PATCH   => "PATCH",
-                Self::DELETE  => "DELETE",
-                Self::HEAD    => "HEAD",
-                Self::OPTIONS => "OPTIONS",
-            })
+            f.write_str(self.as_str())
         }
     }
 };
@@ -18,6 +18,14 @@ pub(crate) use store::Store;
 mod from_request; 
 pub use from_request::*;
 
+#[cfg(feature="__rt_native__")]
+mod peer;
+#[cfg(feature="__rt_native__")]
+pub use peer::{PeerAddr, ProxyInfo, Disconnected};
+
+#[cfg(any(feature="__rt_native__", all(feature="rt_worker", feature="testing")))]
+mod parse;
+
 #[cfg(test)] mod _test_parse;
 #[cfg(test)] mod _test_extract;
 #[cfg(test)] mod _test_headers;
@@ -25,21 +33,19 @@ pub use from_request::*;
 use ohkami_lib::{Slice, CowSlice};
 
 #[cfg(feature="__rt_native__")]
-use crate::__rt__::AsyncReader;
+use {
+    crate::__rt__::AsyncReader,
+    crate::util::{timeout_in, until},
+    std::sync::atomic::{AtomicUsize, Ordering},
+};
 
 #[allow(unused)]
 use {
-    byte_reader::Reader,
     std::pin::Pin,
     std::borrow::Cow,
 };
 
 
-#[cfg(feature="__rt_native__")]
-pub(crate) const BUF_SIZE: usize = 1 << 10;
-#[cfg(feature="__rt_native__")]
-pub(crate) const PAYLOAD_LIMIT: usize = 1 << 32;
-
 /// # HTTP Request
 /// 
 /// Composed of
@@ -49,7 +55,7 @@ pub(crate) const PAYLOAD_LIMIT: usize = 1 << 32;
 /// - `path`
 /// - `queries`
 /// - `payload`
-/// - `ip`
+/// - `ip` and `peer`
 /// 
 /// and a `memory`.
 /// 
@@ -99,7 +105,19 @@ pub(crate) const PAYLOAD_LIMIT: usize = 1 << 32;
 /// ```
 pub struct Request {
     #[cfg(feature="__rt_native__")]
-    pub(super/* for test */) __buf__: Box<[u8; BUF_SIZE]>,
+    pub(super/* for test */) __buf__: Box<[u8]>,
+    #[cfg(feature="__rt_native__")]
+    pub(super/* for test */) __parser__: parse::Parser,
+    /// Bytes in `__buf__` read beyond this request, that is,
+    /// the beginning of the next pipelined request
+    #[cfg(feature="__rt_native__")]
+    pub(super/* for test */) __rest__: std::ops::Range<usize>,
+    /// Bytes read beyond a chunked body, which don't fit in `__rest__`
+    #[cfg(feature="__rt_native__")]
+    pub(super/* for test */) __spilled__: Vec<u8>,
+    /// Head of an HTTP/2 request, which `path`, `query` and `headers` refer to
+    #[cfg(all(feature="http2", feature="__rt_native__"))]
+    pub(super/* for test */) __head__: Option<http::request::Parts>,
 
     #[cfg(feature="rt_worker")]
     pub(super/* for test */) __url__: std::mem::MaybeUninit<::worker::Url>,
@@ -112,10 +130,15 @@ pub struct Request {
     /// 
     /// ---
     /// 
-    /// **Note** : In current version, custom HTTP methods are *not supported*,
-    /// in other words, now Ohkami just knows `GET`, `PUT`, `POST`, `PATCH`,
-    /// `DELETE`, `HEAD`, `OPTIONS`.
+    /// **Note** : Besides `GET`, `PUT`, `POST`, `PATCH`, `DELETE`, `HEAD`, `OPTIONS`,
+    /// this can be an extension method routed by `.method(name, handler)`
+    /// ( `Method::Extension` ). Requests of the other methods are answered
+    /// `501 Not Implemented` before any fang or handler.
     pub method: Method,
+    /// Method token that's not a standard one, which the router resolves
+    /// into `method` among its extension methods
+    #[cfg(feature="__rt__")]
+    pub(crate) extension_method: Option<Slice>,
 
     /// Request path of this request
     /// 
@@ -124,9 +147,10 @@ pub struct Request {
     /// 
     /// ---
     /// 
-    /// **Note** : In current version, path with schema and origin in request line
-    /// is *not supported*, in other words, now Ohkami just handles requests like
-    /// `GET /path HTTP/1.1`, not `GET http://whatthe.fxxx/path HTTP/1.1`
+    /// **Note** : For absolute-form request target like
+    /// `GET http://example.com/path?q=1 HTTP/1.1`, this is `/path` and
+    /// the authority `example.com` is exposed as `headers.Host()`,
+    /// taking the place of `Host` header ( RFC 9112, 3.2.2 ).
     pub path: Path,
 
     /// Query params of this request
@@ -168,7 +192,109 @@ pub struct Request {
     /// ---
     /// 
     /// **NOTE** : If a proxy is in front of Ohkami, this will be the proxy's address
+    /// unless the proxy sends the client's one by PROXY protocol ( see `ServerConfig::proxy_protocol` ).
+    /// For a Unix domain socket, this is `0.0.0.0` ( see `peer` ).
     pub ip: std::net::IpAddr,
+
+    #[cfg(feature="__rt_native__")]
+    /// Remote ( directly connected ) peer's address, including Unix domain socket's one
+    pub peer: PeerAddr,
+
+    #[cfg(feature="__rt_native__")]
+    pub(crate) proxy: Option<std::sync::Arc<ProxyInfo>>,
+
+    #[cfg(feature="__rt_native__")]
+    pub(crate) disconnected: Disconnected,
+
+    #[cfg(all(feature="tls", feature="__rt_native__"))]
+    pub(crate) tls: Option<std::sync::Arc<crate::tls::TlsInfo>>,
+}
+
+/// `read_exact` counting bytes into `received` as they arrive
+#[cfg(feature="__rt_native__")]
+async fn read_exact(
+    stream:   &mut (impl AsyncReader + Unpin),
+    buf:      &mut [u8],
+    received: &AtomicUsize,
+) -> std::io::Result<()> {
+    let mut filled = 0;
+    while filled < buf.len() {
+        match stream.read(&mut buf[filled..]).await? {
+            0 => return Err(std::io::ErrorKind::UnexpectedEof.into()),
+            n => {
+                filled += n;
+                received.fetch_add(n, Ordering::Relaxed);
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Completes when the bytes `received` so far are fewer than `min_rate` bytes/sec
+/// on average, checked every second ( never without `min_rate` )
+#[cfg(feature="__rt_native__")]
+async fn too_slow(min_rate: Option<usize>, received: &AtomicUsize) {
+    let Some(min_rate) = min_rate else {
+        return std::future::pending().await
+    };
+    let start = std::time::Instant::now();
+    loop {
+        crate::__rt__::sleep(std::time::Duration::from_secs(1)).await;
+        if (received.load(Ordering::Relaxed) as f64) < min_rate as f64 * start.elapsed().as_secs_f64() {
+            crate::DEBUG!("Request body is arriving slower than {min_rate} bytes/sec");
+            break
+        }
+    }
+}
+
+#[cfg(any(feature="__rt_native__", all(feature="rt_worker", feature="testing")))]
+fn parse_error<T>(err: parse::Error) -> Result<Option<T>, Box<crate::Response>> {
+    use crate::Response;
+
+    match err {
+        parse::Error::UnknownMethod             => Ok(None),
+        parse::Error::BadRequest                => Err(Box::new(Response::BadRequest())),
+        parse::Error::VersionNotSupported       => Err(Box::new(Response::HTTPVersionNotSupported())),
+        parse::Error::TooManyHeaders            => Err(Box::new(Response::RequestHeaderFieldsTooLarge())),
+        parse::Error::PayloadTooLarge           => Err(Box::new(Response::PayloadTooLarge())),
+        parse::Error::UnsupportedTransferCoding => Err(Box::new(Response::NotImplemented())),
+    }
+}
+
+/// Why reading a request failed
+#[cfg(feature="__rt_native__")]
+#[derive(Debug)]
+pub(crate) enum ReadError {
+    /// to be responded before closing the connection
+    Respond(Box<crate::Response>),
+    /// of the connection itself, so no response can be sent
+    Io(std::io::Error),
+}
+#[cfg(feature="__rt_native__")]
+const _: () = {
+    impl From<crate::Response> for ReadError {
+        fn from(res: crate::Response) -> Self {
+            Self::Respond(Box::new(res))
+        }
+    }
+    impl From<Box<crate::Response>> for ReadError {
+        fn from(res: Box<crate::Response>) -> Self {
+            Self::Respond(res)
+        }
+    }
+    impl From<std::io::Error> for ReadError {
+        fn from(err: std::io::Error) -> Self {
+            Self::Io(err)
+        }
+    }
+};
+
+/// How the body of a request is delimited
+#[cfg(any(feature="__rt_native__", all(feature="rt_worker", feature="testing")))]
+pub(crate) enum Framing {
+    None,
+    Length(usize),
+    Chunked,
 }
 
 impl Request {
@@ -176,11 +302,19 @@ impl Request {
     #[inline]
     pub(crate) fn init(
         #[cfg(feature="__rt_native__")]
-        ip: std::net::IpAddr
+        peer: PeerAddr
     ) -> Self {
         Self {
             #[cfg(feature="__rt_native__")]
-            __buf__: Box::new([0; BUF_SIZE]),
+            __buf__: Box::new([]),
+            #[cfg(feature="__rt_native__")]
+            __parser__: parse::Parser::new(),
+            #[cfg(feature="__rt_native__")]
+            __rest__: 0..0,
+            #[cfg(feature="__rt_native__")]
+            __spilled__: Vec::new(),
+            #[cfg(all(feature="http2", feature="__rt_native__"))]
+            __head__: None,
 
             #[cfg(feature="rt_worker")]
             __url__: std::mem::MaybeUninit::uninit(),
@@ -190,6 +324,8 @@ impl Request {
             ctx:     std::mem::MaybeUninit::uninit(),
 
             method:  Method::GET,
+            #[cfg(feature="__rt__")]
+            extension_method: None,
             path:    Path::uninit(),
             query:   None,
             headers: RequestHeaders::init(),
@@ -197,22 +333,127 @@ impl Request {
             store:   Store::init(),
             
             #[cfg(feature="__rt_native__")]
-            ip,
+            ip: peer.ip().unwrap_or(crate::util::IP_0000),
+            #[cfg(feature="__rt_native__")]
+            peer,
+
+            #[cfg(feature="__rt_native__")]
+            proxy: None,
+
+            #[cfg(feature="__rt_native__")]
+            disconnected: Disconnected(None),
+
+            #[cfg(all(feature="tls", feature="__rt_native__"))]
+            tls: None,
         }
     }
     #[cfg(feature="__rt_native__")]
     #[inline]
     pub(crate) fn clear(&mut self) {
-        if self.__buf__[0] != 0 {
-            for b in &mut *self.__buf__ {
-                match b {0 => break, _ => *b = 0}
-            }
+        if self.__rest__.end != 0 {
+            self.__parser__.reset();
             self.path  = Path::uninit();
             self.query = None;
             self.headers.clear();
             self.payload = None;
             self.store.clear();
-        } /* else: just after `init`ed or `clear`ed */
+
+            /* keep bytes of the next pipelined request at the front of the buffer */
+            if self.__spilled__.is_empty() {
+                self.__buf__.copy_within(self.__rest__.clone(), 0);
+                self.__rest__ = 0..self.__rest__.len();
+            } else {
+                if self.__buf__.len() < self.__spilled__.len() {
+                    self.__buf__ = vec![0; self.__spilled__.len()].into_boxed_slice();
+                }
+                self.__buf__[..self.__spilled__.len()].copy_from_slice(&self.__spilled__);
+                self.__rest__ = 0..self.__spilled__.len();
+                self.__spilled__.clear();
+            }
+        } /* else: just after `init`ed */
+    }
+
+    /// Keep bytes read from the connection while handling this request,
+    /// that is, the beginning of the next pipelined request
+    #[cfg(feature="__rt_native__")]
+    pub(crate) fn read_ahead(&mut self, bytes: &[u8]) {
+        if self.__spilled__.is_empty() {
+            self.__spilled__.extend_from_slice(&self.__buf__[self.__rest__.clone()]);
+        }
+        self.__spilled__.extend_from_slice(bytes);
+    }
+
+    /// Seed the buffer with bytes already read from the connection
+    /// ( while checking for the HTTP/2 connection preface )
+    #[cfg(all(feature="http2", feature="__rt_native__"))]
+    pub(crate) fn prefill(&mut self, bytes: Vec<u8>, config: &crate::ServerConfig) {
+        let len = bytes.len();
+        let mut buf = bytes;
+        buf.resize(len.max(config.header_buffer_size), 0);
+        self.__buf__  = buf.into_boxed_slice();
+        self.__rest__ = 0..len;
+    }
+
+    /// `method` is left to the router when it's not a standard one.
+    /// `name` must be alive as long as this request, like the other request bytes
+    #[cfg(feature="__rt__")]
+    fn set_method(&mut self, name: &[u8]) {
+        match Method::from_bytes(name) {
+            Some(method) => {self.method = method; self.extension_method = None}
+            None => self.extension_method = Some(Slice::from_bytes(name)),
+        }
+    }
+
+    /// Take over the head and the collected body of an HTTP/2 request,
+    /// merging `:authority` into `Host` and split `Cookie`s into one ( RFC 9113, 8.2.3, 8.3.1 )
+    #[cfg(all(feature="http2", feature="__rt_native__"))]
+    pub(crate) fn take_over_h2(&mut self, head: http::request::Parts, payload: Vec<u8>) -> Result<(), Box<crate::Response>> {
+        // SAFETY: `self.__head__` is not modified until this request is dropped,
+        // so the bytes in it live as long as this request is alive
+        let head = unsafe {&*(self.__head__.insert(head) as *const http::request::Parts)};
+
+        self.set_method(head.method.as_str().as_bytes());
+
+        self.path.init_with_request_bytes(head.uri.path().as_bytes())?;
+        if let Some(query) = head.uri.query() {
+            self.query = Some(QueryParams::new(query.as_bytes()));
+        }
+
+        for (name, value) in &head.headers {
+            let (name, value) = (name.as_str().as_bytes(), CowSlice::Ref(Slice::from_bytes(value.as_bytes())));
+            match RequestHeader::from_bytes_ignore_ascii_case(name) {
+                Some(RequestHeader::Cookie) => continue,
+                Some(key) => self.headers.append(key, value),
+                None      => self.headers.insert_custom(Slice::from_bytes(name), value),
+            }
+        }
+        let mut cookies = head.headers.get_all(http::header::COOKIE).iter();
+        if let Some(first) = cookies.next() {
+            let mut cookie = CowSlice::Ref(Slice::from_bytes(first.as_bytes()));
+            for next in cookies {
+                unsafe {
+                    cookie.extend_from_slice(b"; ");
+                    cookie.extend_from_slice(next.as_bytes());
+                }
+            }
+            self.headers.insert(RequestHeader::Cookie, cookie);
+        }
+        if let Some(authority) = head.uri.authority() {
+            self.headers.insert(RequestHeader::Host, CowSlice::Ref(Slice::from_bytes(authority.as_str().as_bytes())));
+        }
+
+        if !payload.is_empty() {
+            self.payload = Some(CowSlice::Own(payload.into_boxed_slice()));
+        }
+
+        Ok(())
+    }
+
+    /// Whether this is an `HTTP/1.0` request, available after `read_head`
+    #[cfg(feature="__rt_native__")]
+    #[inline]
+    pub(crate) fn is_http10(&self) -> bool {
+        self.__parser__.is_http10()
     }
 
     #[cfg(feature="__rt_native__")]
@@ -220,73 +461,190 @@ impl Request {
     pub(crate) async fn read(
         mut self: Pin<&mut Self>,
         stream:   &mut (impl AsyncReader + Unpin),
-    ) -> Result<Option<()>, crate::Response> {
-        use crate::Response;
-
-        match stream.read(&mut *self.__buf__).await {
-            Ok (0) => return Ok(None),
-            Err(e) => return match e.kind() {
-                std::io::ErrorKind::ConnectionReset => Ok(None),
-                _ => Err((|err| {
-                    crate::warning!("Failed to read stream: {err}");
-                    Response::InternalServerError()
-                })(e))
-            },
-            _ => ()
-        }
-
-        let mut r = Reader::new(unsafe {
-            // pass detouched bytes
-            // to resolve immutable/mutable borrowing
-            // 
-            // SAFETY: `self.__buf__` itself is immutable
-            Slice::from_bytes(&*self.__buf__).as_bytes()
-        });
-
-        match Method::from_bytes(r.read_while(|b| b != &b' ')) {
-            None => return Ok(None),
-            Some(method) => self.method = method
+        config:   &crate::ServerConfig,
+    ) -> Result<Option<()>, ReadError> {
+        match self.as_mut().read_head(stream, config, std::future::pending()).await? {
+            None          => Ok(None),
+            Some(framing) => self.read_body(stream, config, framing).await.map(Some),
         }
+    }
 
-        r.next_if(|b| *b==b' ').ok_or_else(Response::BadRequest)?;
-        
-        self.path.init_with_request_bytes(r.read_while(|b| !matches!(b, b' ' | b'?')))?;
+    /// Read the request line and headers, leaving the body in `stream`
+    /// ( or `__rest__` ) to let the caller respond to `Expect` before it.
+    /// 
+    /// Returns `Ok(None)` without reading when `shutdown` completes while idle.
+    #[cfg(feature="__rt_native__")]
+    pub(crate) async fn read_head(
+        mut self: Pin<&mut Self>,
+        stream:   &mut (impl AsyncReader + Unpin),
+        config:   &crate::ServerConfig,
+        shutdown: impl std::future::Future<Output = ()>,
+    ) -> Result<Option<Framing>, ReadError> {
+        use crate::Response;
 
-        if r.consume_oneof([" ", "?"]).unwrap() == 1 {
-            self.query = Some(QueryParams::new(r.read_while(|b| b != &b' ')));
-            r.advance_by(1);
+        if self.__buf__.is_empty() {
+            /* just after `init`ed, and kept ( maybe grown ) for later requests */
+            self.__buf__ = vec![0; config.header_buffer_size].into_boxed_slice();
         }
 
-        r.consume("HTTP/1.1\r\n").ok_or_else(Response::HTTPVersionNotSupported)?;
+        /* idle: waiting for the first bytes of the next request, unless already read by pipelining */
+        let mut filled = match self.__rest__.len() {
+            0 => match until(shutdown, timeout_in(config.keepalive_timeout, stream.read(&mut self.__buf__))).await.flatten() {
+                None         => return Ok(None),
+                Some(Ok(0))  => return Ok(None),
+                Some(Ok(n))  => n,
+                Some(Err(e)) => return Err(e.into()),
+            }
+            pipelined => pipelined,
+        };
 
-        while r.consume("\r\n").is_none() {
-            let key_bytes = r.read_while(|b| b != &b':');
-            r.consume(": ").ok_or_else(Response::BadRequest)?;
-            let value = CowSlice::Ref(Slice::from_bytes(r.read_while(|b| b != &b'\r')));
-            r.consume("\r\n").ok_or_else(Response::BadRequest)?;
+        /* headers: reading until the head is complete, growing the buffer up to `max_header_size` */
+        let this = &mut *self;
+        let head_len = match this.__parser__.parse(&this.__buf__[..filled], config.max_headers, config.strict_parsing) {
+            Ok(parse::Status::Complete(head_len)) => head_len,
+            Err(e) => return parse_error(e).map_err(ReadError::from),
+            Ok(parse::Status::Partial) => {
+                let head = timeout_in(config.header_read_timeout, async {
+                    loop {
+                        if filled == this.__buf__.len() {
+                            if filled >= config.max_header_size {
+                                return Err(Err(Response::RequestHeaderFieldsTooLarge().into()))
+                            }
+                            let mut grown = vec![0; (2 * filled).min(config.max_header_size)].into_boxed_slice();
+                            grown[..filled].copy_from_slice(&this.__buf__[..filled]);
+                            this.__buf__ = grown;
+                        }
+
+                        match stream.read(&mut this.__buf__[filled..]).await {
+                            Ok(0)  => return Err(Ok(None)),
+                            Ok(n)  => filled += n,
+                            Err(e) => return Err(Err(e.into())),
+                        }
+                        match this.__parser__.parse(&this.__buf__[..filled], config.max_headers, config.strict_parsing) {
+                            Ok(parse::Status::Complete(head_len)) => break Ok(head_len),
+                            Ok(parse::Status::Partial) => (),
+                            Err(e) => return Err(parse_error(e).map_err(ReadError::from)),
+                        }
+                    }
+                }).await;
+                match head {
+                    None            => return Err(Response::RequestTimeout().into()),
+                    Some(Ok(len))   => len,
+                    Some(Err(early)) => return early,
+                }
+            }
+        };
 
-            if let Some(key) = RequestHeader::from_bytes(key_bytes) {
-                self.headers.append(key, value);
+        // SAFETY: `self.__buf__` is not modified until `clear`,
+        // so these bytes live as long as this request is alive
+        let buf = unsafe {Slice::from_bytes(&this.__buf__[..filled]).as_bytes()};
+
+        this.set_method(&buf[this.__parser__.method()]);
+        this.path.init_with_request_bytes(match this.__parser__.path() {
+            empty if empty.is_empty() => b"/",
+            path => &buf[path],
+        })?;
+        if let Some(query) = this.__parser__.query() {
+            this.query = Some(QueryParams::new(&buf[query]));
+        }
+        for (name, value) in this.__parser__.headers() {
+            let (name, value) = (&buf[name], CowSlice::Ref(Slice::from_bytes(&buf[value])));
+            if let Some(key) = RequestHeader::from_bytes(name).or_else(|| RequestHeader::from_bytes_ignore_ascii_case(name)) {
+                this.headers.append(key, value);
             } else {
-                self.headers.insert_custom(Slice::from_bytes(key_bytes), value)
+                this.headers.insert_custom(Slice::from_bytes(name), value)
             }
         }
+        if let Some(authority) = this.__parser__.authority() {
+            /* absolute-form target overrides `Host` ( RFC 9112, 3.2.2 ) */
+            this.headers.insert(RequestHeader::Host, CowSlice::Ref(Slice::from_bytes(&buf[authority])));
+        }
 
-        let content_length = match self.headers.get_raw(RequestHeader::ContentLength) {
-            Some(v) => unsafe {v.as_bytes()}.into_iter().fold(0, |len, b| 10*len + (*b - b'0') as usize),
-            None    => 0,
+        let framing = match self.framing(config.strict_parsing, self.__parser__.is_http10()) {
+            Ok(framing) => framing,
+            Err(e)      => return parse_error(e).map_err(ReadError::from),
         };
-        match content_length {
-            0 => (),
-            PAYLOAD_LIMIT.. => return Err((|| Response::PayloadTooLarge())()),
-            _ => self.payload = Some(Request::read_payload(
-                stream,
-                r.remaining(),
-                content_length,
-            ).await)
+        self.__rest__ = head_len..filled;
+
+        Ok(Some(framing))
+    }
+
+    #[cfg(feature="__rt_native__")]
+    pub(crate) async fn read_body(
+        mut self: Pin<&mut Self>,
+        stream:   &mut (impl AsyncReader + Unpin),
+        config:   &crate::ServerConfig,
+        framing:  Framing,
+    ) -> Result<(), ReadError> {
+        use crate::Response;
+
+        // SAFETY: `self.__buf__` is not modified until `clear` ( same as `read_head` )
+        let remaining_buf = unsafe {Slice::from_bytes(&self.__buf__[self.__rest__.clone()]).as_bytes()};
+
+        let body_len = match framing {
+            Framing::None => 0,
+            Framing::Chunked => {
+                let received = AtomicUsize::new(remaining_buf.len());
+                let (payload, body_len) = timeout_in(config.body_read_timeout, until(
+                    too_slow(config.min_body_rate, &received),
+                    Request::read_chunked_payload(
+                        stream,
+                        remaining_buf,
+                        config.payload_limit,
+                        &mut self.__spilled__,
+                        &received,
+                    )
+                )).await.flatten().ok_or_else(Response::RequestTimeout)??;
+                self.payload = Some(payload);
+                body_len
+            }
+            Framing::Length(n) if n > config.payload_limit => return Err(Response::PayloadTooLarge().into()),
+            Framing::Length(n) => {
+                let received = AtomicUsize::new(remaining_buf.len());
+                self.payload = Some(timeout_in(config.body_read_timeout, until(
+                    too_slow(config.min_body_rate, &received),
+                    Request::read_payload(
+                        stream,
+                        remaining_buf,
+                        n,
+                        &received,
+                    )
+                )).await.flatten().ok_or_else(Response::RequestTimeout)??);
+                n
+            }
+        };
+        self.__rest__.start += body_len.min(remaining_buf.len());
+
+        Ok(())
+    }
+
+    /// How the body is delimited ( RFC 9112, 6.3 ).
+    /// 
+    /// With `strict`, ambiguous requests that could be framed differently
+    /// by another hop are rejected instead of guessing. That includes
+    /// `Transfer-Encoding` in HTTP/1.0 request ( RFC 9112, 6.1 ).
+    #[cfg(any(feature="__rt_native__", all(feature="rt_worker", feature="testing")))]
+    fn framing(&self, strict: bool, http10: bool) -> Result<Framing, parse::Error> {
+        let transfer_encoding = self.headers.get_raw(RequestHeader::TransferEncoding).map(|v| unsafe {v.as_bytes()});
+        let content_length    = self.headers.get_raw(RequestHeader::ContentLength).map(|v| unsafe {v.as_bytes()});
+
+        if strict {
+            if transfer_encoding.is_some() && (content_length.is_some() || http10) {
+                return Err(parse::Error::BadRequest)
+            }
+            if self.headers.get_raw(RequestHeader::Host).is_some_and(|host| unsafe {host.as_bytes()}.contains(&b',')) {
+                return Err(parse::Error::BadRequest)
+            }
         }
 
-        Ok(Some(()))
+        if let Some(transfer_encoding) = transfer_encoding {
+            parse::check_transfer_encoding(transfer_encoding, strict)?;
+            return Ok(Framing::Chunked)
+        }
+        match content_length {
+            None    => Ok(Framing::None),
+            Some(v) => parse::content_length(v, strict).map(Framing::Length),
+        }
     }
 
     #[cfg(feature="__rt_native__")]
@@ -295,23 +653,24 @@ impl Request {
         stream:        &mut (impl AsyncReader + Unpin),
         remaining_buf: &[u8],
         size:          usize,
-    ) -> CowSlice {
+        received:      &AtomicUsize,
+    ) -> std::io::Result<CowSlice> {
         let remaining_buf_len = remaining_buf.len();
 
-        if remaining_buf_len == 0 || *unsafe {remaining_buf.get_unchecked(0)} == 0 {
-            #[cfg(feature="DEBUG")] println!("\n[read_payload] case: remaining_buf.is_empty() || remaining_buf[0] == 0\n");
+        if remaining_buf_len == 0 {
+            #[cfg(feature="DEBUG")] println!("\n[read_payload] case: remaining_buf.is_empty()\n");
 
             let mut bytes = vec![0; size].into_boxed_slice();
-            stream.read_exact(&mut bytes).await.unwrap();
-            CowSlice::Own(bytes)
+            read_exact(stream, &mut bytes, received).await?;
+            Ok(CowSlice::Own(bytes))
 
         } else if size <= remaining_buf_len {
-            #[cfg(feature="DEBUG")] println!("\n[read_payload] case: starts_at + size <= BUF_SIZE\n");
+            #[cfg(feature="DEBUG")] println!("\n[read_payload] case: size <= remaining_buf_len\n");
 
             #[allow(unused_unsafe/* I don't know why but rustc sometimes put warnings to this unsafe as unnecessary */)]
-            CowSlice::Ref(unsafe {
+            Ok(CowSlice::Ref(unsafe {
                 Slice::new_unchecked(remaining_buf.as_ptr(), size)
-            })
+            }))
 
         } else {
             #[cfg(feature="DEBUG")] println!("\n[read_payload] case: else\n");
@@ -319,9 +678,55 @@ impl Request {
             let mut bytes = vec![0; size].into_boxed_slice();
             unsafe {// SAFETY: Here size > remaining_buf_len
                 bytes.get_unchecked_mut(..remaining_buf_len).copy_from_slice(remaining_buf);
-                stream.read_exact(bytes.get_unchecked_mut(remaining_buf_len..)).await.unwrap();
+                read_exact(stream, bytes.get_unchecked_mut(remaining_buf_len..), received).await?;
+            }
+            Ok(CowSlice::Own(bytes))
+        }
+    }
+
+    /// Returns the decoded payload and how many bytes of `remaining_buf` it took.
+    /// Bytes read from `stream` beyond the body are put into `spilled`.
+    #[cfg(feature="__rt_native__")]
+    async fn read_chunked_payload(
+        stream:        &mut (impl AsyncReader + Unpin),
+        remaining_buf: &[u8],
+        limit:         usize,
+        spilled:       &mut Vec<u8>,
+        received:      &AtomicUsize,
+    ) -> Result<(CowSlice, usize), ReadError> {
+        use crate::Response;
+
+        fn into_response(err: parse::Error) -> Response {
+            match err {
+                parse::Error::PayloadTooLarge => Response::PayloadTooLarge(),
+                _ => Response::BadRequest(),
+            }
+        }
+
+        let mut decoder = parse::ChunkedDecoder::new();
+        let mut payload = Vec::new();
+
+        match decoder.decode(remaining_buf, &mut payload, limit).map_err(into_response)? {
+            parse::Status::Complete(n) => return Ok((CowSlice::Own(payload.into_boxed_slice()), n)),
+            parse::Status::Partial => ()
+        }
+
+        let mut buf = vec![0; 1 << 12];
+        loop {
+            match stream.read(&mut buf).await {
+                Ok(0)  => return Err(Response::BadRequest().into()),
+                Ok(n)  => {
+                    received.fetch_add(n, Ordering::Relaxed);
+                    match decoder.decode(&buf[..n], &mut payload, limit).map_err(into_response)? {
+                        parse::Status::Partial => (),
+                        parse::Status::Complete(m) => {
+                            spilled.extend_from_slice(&buf[m..n]);
+                            break Ok((CowSlice::Own(payload.into_boxed_slice()), remaining_buf.len()))
+                        }
+                    }
+                }
+                Err(e) => return Err(e.into()),
             }
-            CowSlice::Own(bytes)
         }
     }
 
@@ -332,18 +737,21 @@ impl Request {
     ) -> Result<Option<()>, crate::Response> {
         use crate::Response;
 
-        let mut r = Reader::new(raw_bytes);
+        let buf: &[u8] = raw_bytes;
 
-        match Method::from_bytes(r.read_while(|b| b != &b' ')) {
-            None => return Ok(None),
-            Some(method) => self.method = method
-        }
+        let mut parser = parse::Parser::new();
+        let head_len = match parser.parse(buf, usize::MAX, true) {
+            Ok(parse::Status::Complete(head_len)) => head_len,
+            Ok(parse::Status::Partial)            => return Err(Response::BadRequest()),
+            Err(e)                                => return parse_error(e).map_err(|res| *res),
+        };
+
+        self.set_method(&buf[parser.method()]);
 
-        r.next_if(|b| *b==b' ').ok_or_else(Response::BadRequest)?;
-        
         self.__url__.write({
+            let target_end = parser.query().unwrap_or(parser.path()).end;
             let mut url = String::from("http://test.ohkami");
-            url.push_str(std::str::from_utf8(r.read_while(|b| b != &b' ')).unwrap());
+            url.push_str(std::str::from_utf8(&buf[parser.path().start..target_end]).unwrap());
             ::worker::Url::parse(&url).unwrap()
         });
         // SAFETY: Just calling for request bytes and `self.__url__` is already initialized
@@ -353,28 +761,34 @@ impl Request {
             self.path.init_with_request_bytes(path)?;
         }
 
-        r.consume("HTTP/1.1\r\n").ok_or_else(Response::HTTPVersionNotSupported)?;
-
-        while r.consume("\r\n").is_none() {
-            let key_bytes = r.read_while(|b| b != &b':');
-            r.consume(": ").ok_or_else(Response::BadRequest)?;
-            let value = CowSlice::Ref(Slice::from_bytes(r.read_while(|b| b != &b'\r')));
-            r.consume("\r\n").ok_or_else(Response::BadRequest)?;
-
-            if let Some(key) = RequestHeader::from_bytes(key_bytes) {
+        for (name, value) in parser.headers() {
+            let (name, value) = (&buf[name], CowSlice::Ref(Slice::from_bytes(&buf[value])));
+            if let Some(key) = RequestHeader::from_bytes(name).or_else(|| RequestHeader::from_bytes_ignore_ascii_case(name)) {
                 self.headers.append(key, value);
             } else {
-                self.headers.insert_custom(Slice::from_bytes(key_bytes), value)
+                self.headers.insert_custom(Slice::from_bytes(name), value)
             }
         }
+        if let Some(authority) = parser.authority() {
+            self.headers.insert(RequestHeader::Host, CowSlice::Ref(Slice::from_bytes(&buf[authority])));
+        }
 
-        let content_length = match self.headers.get_raw(RequestHeader::ContentLength) {
-            Some(v) => unsafe {v.as_bytes()}.into_iter().fold(0, |len, b| 10*len + (*b - b'0') as usize),
-            None    => 0,
+        let framing = match self.framing(true, parser.is_http10()) {
+            Ok(framing) => framing,
+            Err(e)      => return parse_error(e).map_err(|res| *res),
         };
-        self.payload = (content_length > 0).then(||
-            CowSlice::Own(r.remaining().into())
-        );
+        match framing {
+            Framing::None => (),
+            Framing::Chunked => {
+                let mut payload = Vec::new();
+                match parse::ChunkedDecoder::new().decode(&buf[head_len..], &mut payload, usize::MAX) {
+                    Ok(parse::Status::Complete(_)) => self.payload = Some(CowSlice::Own(payload.into_boxed_slice())),
+                    _ => return Err(Response::BadRequest()),
+                }
+            }
+            Framing::Length(0) => (),
+            Framing::Length(_) => self.payload = Some(CowSlice::Own(buf[head_len..].into())),
+        }
 
         Ok(Some(()))
     }
@@ -432,6 +846,28 @@ impl Request {
         self.payload.as_deref()
     }
 
+    #[cfg(feature="__rt_native__")]
+    /// PROXY protocol header of the connection, `None` unless `ServerConfig::proxy_protocol` is enabled
+    #[inline]
+    pub fn proxy(&self) -> Option<&ProxyInfo> {
+        self.proxy.as_deref()
+    }
+
+    #[cfg(feature="__rt_native__")]
+    /// Notification of the client's disconnection, to be awaited in fangs
+    /// or spawned tasks ( see `Disconnected` )
+    #[inline]
+    pub fn disconnected(&self) -> Disconnected {
+        self.disconnected.clone()
+    }
+
+    #[cfg(all(feature="tls", feature="__rt_native__"))]
+    /// TLS session information, `None` if this request didn't come over TLS
+    #[inline]
+    pub fn tls(&self) -> Option<&crate::tls::TlsInfo> {
+        self.tls.as_deref()
+    }
+
     /// Memorize any data within this request object
     #[inline] pub fn memorize<Value: Send + Sync + 'static>(&mut self, value: Value) {
         self.store.insert(value)
@@ -457,7 +893,7 @@ const _: () = {
                 d.field("payload", &String::from_utf8_lossy(payload));
             }
             #[cfg(feature="__rt_native__")] {
-                d.field("ip", &self.ip);
+                d.field("peer", &self.peer);
             }
 
             d.finish()
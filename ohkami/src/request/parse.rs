@@ -0,0 +1,509 @@
+//! Incremental, sans-IO parser of HTTP/1.x request head
+//! ( request line and headers ) and chunked body
+//!
+//! `Parser` knows nothing about streams: call `parse` with all the bytes
+//! received so far, and read more while it returns `Status::Partial`.
+//! The parsing is resumed from where the previous call stopped, so bytes
+//! can arrive in any fragmentation.
+//!
+//! Results are recorded as ranges in the bytes, so the caller may move
+//! ( e.g. grow ) the buffer between calls.
+//!
+//! `ChunkedDecoder` is the same for `Transfer-Encoding: chunked` body,
+//! except that it takes only *new* bytes on each call because the body
+//! is not kept in the header buffer.
+
+use std::ops::Range;
+use super::method::is_tchar;
+
+
+#[derive(Debug, PartialEq)]
+pub(crate) enum Status {
+    /// The head is not complete yet
+    Partial,
+    /// The head is complete and its length ( including the last `\r\n\r\n` ) is this
+    Complete(usize),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub(crate) enum Error {
+    /// not even a method token, like TLS handshake sent to plain HTTP
+    UnknownMethod,
+    BadRequest,
+    VersionNotSupported,
+    TooManyHeaders,
+    PayloadTooLarge,
+    UnsupportedTransferCoding,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum State {
+    Method,
+    Target,
+    Scheme,
+    SchemeSlashes,
+    Authority,
+    Path,
+    Query,
+    Version,
+    RequestLineLF,
+    HeaderStart,
+    HeaderName,
+    HeaderValueStart,
+    HeaderValue,
+    HeaderLF,
+    HeadLF,
+    Done,
+}
+
+pub(crate) struct Parser {
+    state:   State,
+    /// next byte to look at
+    pos:     usize,
+    /// start of the token being parsed
+    mark:    usize,
+
+    method:    Range<usize>,
+    authority: Option<Range<usize>>,
+    path:      Range<usize>,
+    query:     Option<Range<usize>>,
+    http10:    bool,
+    name:      Range<usize>,
+    headers:   Vec<(Range<usize>, Range<usize>)>,
+}
+
+impl Parser {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state:     State::Method,
+            pos:       0,
+            mark:      0,
+            method:    0..0,
+            authority: None,
+            path:      0..0,
+            query:     None,
+            http10:    false,
+            name:      0..0,
+            headers:   Vec::new(),
+        }
+    }
+
+    #[cfg(feature="__rt_native__")]
+    /// Reset for the next request, keeping allocated capacity
+    pub(crate) fn reset(&mut self) {
+        self.state     = State::Method;
+        self.pos       = 0;
+        self.mark      = 0;
+        self.method    = 0..0;
+        self.authority = None;
+        self.path      = 0..0;
+        self.query     = None;
+        self.http10    = false;
+        self.name      = 0..0;
+        self.headers.clear();
+    }
+
+    /// `bytes` must start with the bytes given to the previous calls
+    /// since `new` or `reset`.
+    /// 
+    /// With `strict`, header names must be tokens and request target and
+    /// header values must not contain control characters ( RFC 9112, 3 and 5 ).
+    pub(crate) fn parse(&mut self, bytes: &[u8], max_headers: usize, strict: bool) -> Result<Status, Error> {
+        while self.pos < bytes.len() {
+            let b = bytes[self.pos];
+
+            match self.state {
+                State::Method => match b {
+                    /* leading empty lines are ignored ( RFC 9112, 2.2 ) */
+                    b'\r' | b'\n' if self.mark == self.pos => self.mark += 1,
+                    b' ' => {
+                        if self.mark == self.pos {
+                            return Err(Error::UnknownMethod)
+                        }
+                        self.method = self.mark..self.pos;
+                        self.mark  = self.pos + 1;
+                        self.state = State::Target;
+                    }
+                    _ if is_tchar(b) => (),
+                    _ => return Err(Error::UnknownMethod)
+                }
+
+                /* origin-form `/path?query` or absolute-form `http://authority/path?query` ( RFC 9112, 3.2 ) */
+                State::Target => {
+                    self.state = if b.is_ascii_alphabetic() {State::Scheme} else {State::Path};
+                    continue
+                }
+
+                State::Scheme => match b {
+                    b':' => {
+                        let scheme = &bytes[self.mark..self.pos];
+                        if !(scheme.eq_ignore_ascii_case(b"http") || scheme.eq_ignore_ascii_case(b"https")) {
+                            return Err(Error::BadRequest)
+                        }
+                        self.mark  = self.pos + 1;
+                        self.state = State::SchemeSlashes;
+                    }
+                    _ if b.is_ascii_alphanumeric() || matches!(b, b'+' | b'-' | b'.') => (),
+                    _ => return Err(Error::BadRequest)
+                }
+
+                State::SchemeSlashes => match b {
+                    b'/' if self.pos == self.mark => (),
+                    b'/' if self.pos == self.mark + 1 => {
+                        self.mark  = self.pos + 1;
+                        self.state = State::Authority;
+                    }
+                    _ => return Err(Error::BadRequest)
+                }
+
+                State::Authority => match b {
+                    b'/' | b'?' | b' ' => {
+                        if self.pos == self.mark {
+                            return Err(Error::BadRequest)
+                        }
+                        self.authority = Some(self.mark..self.pos);
+                        match b {
+                            b'/' => {
+                                self.mark  = self.pos;
+                                self.state = State::Path;
+                            }
+                            _ => {
+                                /* empty path, to be `/` */
+                                self.path  = self.pos..self.pos;
+                                self.mark  = self.pos + 1;
+                                self.state = if b == b' ' {State::Version} else {State::Query};
+                            }
+                        }
+                    }
+                    /* userinfo is deprecated and never sent in a request ( RFC 9110, 4.2.4 ) */
+                    b'@' => return Err(Error::BadRequest),
+                    _ if is_ctl(b) => return Err(Error::BadRequest),
+                    _ => ()
+                }
+
+                State::Path => match b {
+                    b' ' | b'?' => {
+                        self.path  = self.mark..self.pos;
+                        self.mark  = self.pos + 1;
+                        self.state = if b == b' ' {State::Version} else {State::Query};
+                    }
+                    b'\r' | b'\n' => return Err(Error::BadRequest),
+                    _ if strict && is_ctl(b) => return Err(Error::BadRequest),
+                    _ => ()
+                }
+
+                State::Query => match b {
+                    b' ' => {
+                        self.query = Some(self.mark..self.pos);
+                        self.mark  = self.pos + 1;
+                        self.state = State::Version;
+                    }
+                    b'\r' | b'\n' => return Err(Error::BadRequest),
+                    _ if strict && is_ctl(b) => return Err(Error::BadRequest),
+                    _ => ()
+                }
+
+                State::Version => match b {
+                    b'\r' => {
+                        self.http10 = match &bytes[self.mark..self.pos] {
+                            b"HTTP/1.1" => false,
+                            b"HTTP/1.0" => true,
+                            _ => return Err(Error::VersionNotSupported)
+                        };
+                        self.state = State::RequestLineLF;
+                    }
+                    b'\n' => return Err(Error::BadRequest),
+                    _ => ()
+                }
+
+                State::RequestLineLF | State::HeaderLF => match b {
+                    b'\n' => self.state = State::HeaderStart,
+                    _ => return Err(Error::BadRequest)
+                }
+
+                State::HeaderStart => match b {
+                    b'\r' => self.state = State::HeadLF,
+                    _ => {
+                        if self.headers.len() >= max_headers {
+                            return Err(Error::TooManyHeaders)
+                        }
+                        self.mark  = self.pos;
+                        self.state = State::HeaderName;
+                        continue
+                    }
+                }
+
+                State::HeaderName => match b {
+                    b':' if self.mark < self.pos => {
+                        self.name  = self.mark..self.pos;
+                        self.state = State::HeaderValueStart;
+                    }
+                    b':' | b'\r' | b'\n' => return Err(Error::BadRequest),
+                    _ if strict && !is_tchar(b) => return Err(Error::BadRequest),
+                    _ => ()
+                }
+
+                State::HeaderValueStart => match b {
+                    b' ' | b'\t' => (),
+                    _ => {
+                        self.mark  = self.pos;
+                        self.state = State::HeaderValue;
+                        continue
+                    }
+                }
+
+                State::HeaderValue => match b {
+                    b'\r' => {
+                        let mut end = self.pos;
+                        while end > self.mark && matches!(bytes[end-1], b' ' | b'\t') {end -= 1}
+                        self.headers.push((self.name.clone(), self.mark..end));
+                        self.state = State::HeaderLF;
+                    }
+                    b'\n' => return Err(Error::BadRequest),
+                    b'\t' => (),
+                    _ if strict && is_ctl(b) => return Err(Error::BadRequest),
+                    _ => ()
+                }
+
+                State::HeadLF => match b {
+                    b'\n' => {
+                        self.pos  += 1;
+                        self.state = State::Done;
+                        return Ok(Status::Complete(self.pos))
+                    }
+                    _ => return Err(Error::BadRequest)
+                }
+
+                State::Done => return Ok(Status::Complete(self.pos))
+            }
+
+            self.pos += 1;
+        }
+
+        Ok(if self.state == State::Done {Status::Complete(self.pos)} else {Status::Partial})
+    }
+
+    /// Method token, maybe of an extension method. Available after `Status::Complete`
+    pub(crate) fn method(&self) -> Range<usize> {
+        self.method.clone()
+    }
+    /// Authority of absolute-form request target
+    pub(crate) fn authority(&self) -> Option<Range<usize>> {
+        self.authority.clone()
+    }
+    /// Empty for absolute-form request target without path
+    pub(crate) fn path(&self) -> Range<usize> {
+        self.path.clone()
+    }
+    pub(crate) fn query(&self) -> Option<Range<usize>> {
+        self.query.clone()
+    }
+    /// `HTTP/1.0`, otherwise `HTTP/1.1`
+    pub(crate) fn is_http10(&self) -> bool {
+        self.http10
+    }
+    /// `(name, value)`s in the order they appeared
+    pub(crate) fn headers(&self) -> impl Iterator<Item = (Range<usize>, Range<usize>)> + '_ {
+        self.headers.iter().cloned()
+    }
+}
+
+/// Body length from `Content-Length` value.
+/// 
+/// Multiple `Content-Length`s are combined as `a, b` in `RequestHeaders`;
+/// they are rejected with `strict`, or accepted only when all the same
+/// ( RFC 9112, 6.3 ) without it.
+pub(crate) fn content_length(value: &[u8], strict: bool) -> Result<usize, Error> {
+    fn digits(bytes: &[u8]) -> Result<usize, Error> {
+        if bytes.is_empty() {
+            return Err(Error::BadRequest)
+        }
+        bytes.iter().try_fold(0_usize, |len, b| match b {
+            b'0'..=b'9' => len.checked_mul(10)
+                .and_then(|len| len.checked_add((b - b'0') as usize))
+                .ok_or(Error::PayloadTooLarge),
+            _ => Err(Error::BadRequest)
+        })
+    }
+
+    if strict {
+        digits(value)
+    } else {
+        let mut values = value.split(|b| *b == b',').map(<[u8]>::trim_ascii);
+        let first = digits(values.next().unwrap_or_default())?;
+        match values.all(|v| digits(v) == Ok(first)) {
+            true  => Ok(first),
+            false => Err(Error::BadRequest)
+        }
+    }
+}
+
+/// Check `Transfer-Encoding` value, only `chunked` is supported.
+/// 
+/// With `strict`, `chunked` must be applied just once and at the end
+/// ( RFC 9112, 6.3 ) and other codings are `UnsupportedTransferCoding`.
+pub(crate) fn check_transfer_encoding(value: &[u8], strict: bool) -> Result<(), Error> {
+    if !strict {
+        return match value.eq_ignore_ascii_case(b"chunked") {
+            true  => Ok(()),
+            false => Err(Error::UnsupportedTransferCoding)
+        }
+    }
+
+    let codings = value.split(|b| *b == b',').map(<[u8]>::trim_ascii).collect::<Vec<_>>();
+    let Some((last, preceding)) = codings.split_last() else {unreachable!()};
+    if !last.eq_ignore_ascii_case(b"chunked")
+    || preceding.iter().any(|c| c.eq_ignore_ascii_case(b"chunked")) {
+        return Err(Error::BadRequest)
+    }
+    match preceding.is_empty() {
+        true  => Ok(()),
+        false => Err(Error::UnsupportedTransferCoding)
+    }
+}
+
+#[inline(always)]
+const fn is_ctl(b: u8) -> bool {
+    matches!(b, 0x00..=0x1F | 0x7F)
+}
+
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Chunked {
+    SizeStart,
+    Size,
+    Extension,
+    SizeLF,
+    Data,
+    DataCR,
+    DataLF,
+    TrailerStart,
+    Trailer,
+    TrailerLF,
+    EndLF,
+}
+
+pub(crate) struct ChunkedDecoder {
+    state:    Chunked,
+    /// size of the current chunk, or its rest while `Chunked::Data`
+    size:     usize,
+    /// bytes of chunk extensions and trailers, counted against the limit with the data
+    overhead: usize,
+}
+
+impl ChunkedDecoder {
+    pub(crate) const fn new() -> Self {
+        Self {
+            state:    Chunked::SizeStart,
+            size:     0,
+            overhead: 0,
+        }
+    }
+
+    /// Decode `bytes` following the ones given to the previous calls,
+    /// appending chunk data to `payload`.
+    /// 
+    /// `Status::Complete` holds the number of bytes consumed *in this call*;
+    /// the rest of `bytes` is not a part of this body.
+    /// 
+    /// Trailer fields are validated as lines and discarded.
+    pub(crate) fn decode(&mut self, bytes: &[u8], payload: &mut Vec<u8>, limit: usize) -> Result<Status, Error> {
+        let mut i = 0;
+        while i < bytes.len() {
+            let b = bytes[i];
+
+            match self.state {
+                Chunked::SizeStart | Chunked::Size => match b {
+                    b'0'..=b'9' | b'a'..=b'f' | b'A'..=b'F' => {
+                        let digit = (b as char).to_digit(16).unwrap() as usize;
+                        self.size = self.size.checked_mul(16)
+                            .and_then(|size| size.checked_add(digit))
+                            .ok_or(Error::PayloadTooLarge)?;
+                        self.state = Chunked::Size;
+                    }
+                    b';' | b' ' | b'\t' if self.state == Chunked::Size => self.state = Chunked::Extension,
+                    b'\r' if self.state == Chunked::Size => self.state = Chunked::SizeLF,
+                    _ => return Err(Error::BadRequest)
+                }
+
+                Chunked::Extension => match b {
+                    b'\r' => self.state = Chunked::SizeLF,
+                    b'\n' => return Err(Error::BadRequest),
+                    _ => self.count_overhead(payload, limit)?
+                }
+
+                Chunked::SizeLF => match b {
+                    b'\n' if self.size == 0 => self.state = Chunked::TrailerStart,
+                    b'\n' => {
+                        if payload.len().checked_add(self.size)
+                            .and_then(|len| len.checked_add(self.overhead))
+                            .is_none_or(|len| len > limit)
+                        {
+                            return Err(Error::PayloadTooLarge)
+                        }
+                        self.state = Chunked::Data;
+                    }
+                    _ => return Err(Error::BadRequest)
+                }
+
+                Chunked::Data => {
+                    let n = self.size.min(bytes.len() - i);
+                    payload.extend_from_slice(&bytes[i..i+n]);
+                    self.size -= n;
+                    if self.size == 0 {
+                        self.state = Chunked::DataCR;
+                    }
+                    i += n;
+                    continue
+                }
+
+                Chunked::DataCR => match b {
+                    b'\r' => self.state = Chunked::DataLF,
+                    _ => return Err(Error::BadRequest)
+                }
+
+                Chunked::DataLF => match b {
+                    b'\n' => self.state = Chunked::SizeStart,
+                    _ => return Err(Error::BadRequest)
+                }
+
+                Chunked::TrailerStart => match b {
+                    b'\r' => self.state = Chunked::EndLF,
+                    _ => {
+                        self.state = Chunked::Trailer;
+                        continue
+                    }
+                }
+
+                Chunked::Trailer => match b {
+                    b'\r' => self.state = Chunked::TrailerLF,
+                    b'\n' => return Err(Error::BadRequest),
+                    _ => self.count_overhead(payload, limit)?
+                }
+
+                Chunked::TrailerLF => match b {
+                    b'\n' => self.state = Chunked::TrailerStart,
+                    _ => return Err(Error::BadRequest)
+                }
+
+                Chunked::EndLF => match b {
+                    b'\n' => return Ok(Status::Complete(i + 1)),
+                    _ => return Err(Error::BadRequest)
+                }
+            }
+
+            i += 1;
+        }
+
+        Ok(Status::Partial)
+    }
+
+    #[inline]
+    fn count_overhead(&mut self, payload: &[u8], limit: usize) -> Result<(), Error> {
+        self.overhead += 1;
+        if payload.len() + self.overhead > limit {
+            return Err(Error::PayloadTooLarge)
+        }
+        Ok(())
+    }
+}
@@ -2,45 +2,59 @@ use std::{borrow::Cow, mem::MaybeUninit};
 use ohkami_lib::{percent_decode_utf8, Slice};
 
 
-pub struct Path(
-    MaybeUninit<PathInner>
-);
-pub(crate) struct PathInner {
-    raw:    Slice,
+pub struct Path {
+    raw:    MaybeUninit<Slice>,
     params: Params,
+    /// names of `params` in the matched route
+    names:  &'static [&'static str],
 }
+/// Path params, held inline up to `Params::INLINE`
+/// and allocating only for more than that
 struct Params {
-    next: usize,
-    list: [MaybeUninit<Slice>; Self::LIMIT]
+    next:     usize,
+    inline:   [MaybeUninit<Slice>; Self::INLINE],
+    overflow: Vec<Slice>,
 }
 impl Params {
-    const LIMIT: usize = 2;
+    const INLINE: usize = 4;
 }
 
 const _: () = {
     impl Params {
         fn iter(&self) -> impl Iterator<Item = &Slice> {
-            (0..self.next).map(|i| unsafe {
-                self.list
-                    .get_unchecked(i)
-                    .assume_init_ref()
-            })
+            (0..self.next).map(|i| unsafe {self.get_unchecked(i)})
+        }
+
+        /// SAFETY: `index` < `self.next`
+        #[inline(always)]
+        unsafe fn get_unchecked(&self, index: usize) -> &Slice {
+            match self.inline.get(index) {
+                Some(param) => param.assume_init_ref(),
+                None        => self.overflow.get_unchecked(index - Self::INLINE),
+            }
         }
     }
 
     impl Path {
         pub fn params(&self) -> impl Iterator<Item = Cow<str>> {
-            unsafe {self.0.assume_init_ref()}
-                .params.iter()
+            self.params.iter()
                 .map(|slice| percent_decode_utf8(unsafe {slice.as_bytes()})
                 .expect("Non UTF-8 path params"))
         }
 
+        /// Iterate path params with their names in the matched route
+        /// like `("id", Ok("42"))` for `/users/:id`, `Err` for a param
+        /// that's not UTF-8 after percent-decoding
+        pub fn named_params(&self) -> impl Iterator<Item = (&'static str, Result<Cow<'_, str>, std::str::Utf8Error>)> {
+            self.names.iter().copied().zip(self.params.iter()
+                .map(|slice| percent_decode_utf8(unsafe {slice.as_bytes()})))
+        }
+
         /// Get request path as `Cow::Borrowed(&str)` if it's not percent-encoded, or,
         /// decode it into `Cow::Owned(String)` if encoded in the original request.
         #[inline]
         pub fn str(&self) -> Cow<str> {
-            let bytes = unsafe {self.0.assume_init_ref().raw.as_bytes()};
+            let bytes = unsafe {self.raw.assume_init_ref().as_bytes()};
             if bytes.is_empty() {return Cow::Borrowed("/")}
             percent_decode_utf8(bytes).expect("Non UTF-8 path params")
         }
@@ -56,7 +70,7 @@ const _: () = {
     impl AsRef<str> for Path {
         #[inline]
         fn as_ref(&self) -> &str {
-            let bytes = &unsafe {self.0.assume_init_ref().raw.as_bytes()};
+            let bytes = &unsafe {self.raw.assume_init_ref().as_bytes()};
             if bytes.is_empty() {return "/"}
             std::str::from_utf8(bytes).expect("Non UTF-8 path params")
         }
@@ -79,26 +93,35 @@ const _: () = {
     impl Params {
         #[inline(always)]
         const fn init() -> Self {
-            Params { next: 0, list: [const {MaybeUninit::uninit()}; Params::LIMIT] }
+            Params {
+                next:     0,
+                inline:   [const {MaybeUninit::uninit()}; Params::INLINE],
+                overflow: Vec::new(),
+            }
         }
-        
+
+        /// Set `index`-th param, discarding the ones after it
         #[inline(always)]
-        fn push(&mut self, param: Slice) {
-            #[cfg(debug_assertions)] {
-                assert!(self.next < Self::LIMIT);
-            }
-            unsafe {self.list
-                .get_unchecked_mut(self.next)
-                .write(param);
+        fn set(&mut self, index: usize, param: Slice) {
+            match self.inline.get_mut(index) {
+                Some(slot) => {slot.write(param);}
+                None => {
+                    self.overflow.truncate(index - Self::INLINE);
+                    self.overflow.push(param);
+                }
             }
-            self.next += 1;
+            self.next = index + 1;
         }
     }
     
     impl Path {
         #[inline]
         pub(crate) fn uninit() -> Self {
-            Self(MaybeUninit::uninit())
+            Self {
+                raw:    MaybeUninit::uninit(),
+                params: Params::init(),
+                names:  &[],
+            }
         }
 
         #[inline(always)]
@@ -119,34 +142,38 @@ const _: () = {
             if unsafe {*bytes.get_unchecked(len-1) == b'/'} {len -= 1};
 
             #[allow(unused_unsafe/* I don't know why but rustc sometimes put warnings to this unsafe as unnecessary */)]
-            Ok({self.0.write(PathInner {
-                raw:    unsafe {Slice::new_unchecked(bytes.as_ptr(), len)},
-                params: Params::init(),
-            });})
+            Ok({
+                self.raw.write(unsafe {Slice::new_unchecked(bytes.as_ptr(), len)});
+                self.params.next = 0;
+                self.names = &[];
+            })
         }
 
-        #[inline] pub(crate) unsafe fn push_param(&mut self, param: Slice) {
-            self.0.assume_init_mut().params.push(param)
+        /// Set `index`-th path param, discarding the ones after it
+        #[inline] pub(crate) unsafe fn set_param(&mut self, index: usize, param: Slice) {
+            self.params.set(index, param)
         }
-        #[inline] pub(crate) unsafe fn assume_one_param<'p>(&self) -> &'p [u8] {
-            self.0.assume_init_ref().params.list.get_unchecked(0).assume_init_ref().as_bytes()
+        #[inline] pub(crate) fn set_param_names(&mut self, names: &'static [&'static str]) {
+            self.names = names
         }
-        #[inline] pub(crate) unsafe fn assume_two_params<'p>(&self) -> (&'p [u8], &'p [u8]) {
-            (self.0.assume_init_ref().params.list.get_unchecked(0).assume_init_ref().as_bytes(), self.0.assume_init_ref().params.list.get_unchecked(1).assume_init_ref().as_bytes())
+        /// SAFETY: the router has set at least `N` params
+        #[inline] pub(crate) unsafe fn assume_params<'p, const N: usize>(&self) -> [&'p [u8]; N] {
+            std::array::from_fn(|i| self.params.get_unchecked(i).as_bytes())
         }
 
         #[inline] pub(crate) unsafe fn normalized_bytes<'req>(&self) -> &'req [u8] {
-            self.0.assume_init_ref().raw.as_bytes()
+            self.raw.assume_init_ref().as_bytes()
         }
     }
     
     #[cfg(test)]
     impl Path {
         pub fn from_literal(literal: &'static str) -> Self {
-            Self(MaybeUninit::new(PathInner {
-                raw:    Slice::from_bytes(literal.as_bytes()),
+            Self {
+                raw:    MaybeUninit::new(Slice::from_bytes(literal.as_bytes())),
                 params: Params::init(),
-            }))
+                names:  &[],
+            }
         }
     }
 };
@@ -0,0 +1,158 @@
+#![cfg(feature="__rt_native__")]
+
+use std::net::{IpAddr, SocketAddr};
+
+
+/// Address of the directly connected peer
+#[derive(Clone, Debug, PartialEq)]
+pub enum PeerAddr {
+    Tcp(SocketAddr),
+
+    /// Peer of a Unix domain socket, usually unnamed ( `None` )
+    #[cfg(unix)]
+    Unix(Option<std::path::PathBuf>),
+}
+
+impl PeerAddr {
+    /// IP address of the peer, `None` for a Unix domain socket
+    pub fn ip(&self) -> Option<IpAddr> {
+        match self {
+            Self::Tcp(addr) => Some(addr.ip()),
+            #[cfg(unix)]
+            Self::Unix(_)   => None,
+        }
+    }
+}
+
+impl std::fmt::Display for PeerAddr {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Tcp(addr) => addr.fmt(f),
+            #[cfg(unix)]
+            Self::Unix(Some(path)) => write!(f, "unix:{}", path.display()),
+            #[cfg(unix)]
+            Self::Unix(None)       => f.write_str("unix:(unnamed)"),
+        }
+    }
+}
+
+/// Header of PROXY protocol v1 / v2 sent by a load balancer at the start
+/// of the connection ( with `ServerConfig::proxy_protocol` )
+///
+/// Available in handlers as `&ProxyInfo`. When it carries the client's address,
+/// that is also set to `Request::ip` and `Request::peer`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ProxyInfo {
+    pub(crate) source:      Option<SocketAddr>,
+    pub(crate) destination: Option<SocketAddr>,
+    pub(crate) tlvs:        Vec<(u8, Box<[u8]>)>,
+}
+impl ProxyInfo {
+    /// Address of the original client, `None` for `UNKNOWN` ( v1 ),
+    /// `LOCAL` or non-IP address family ( v2 )
+    pub fn source(&self) -> Option<SocketAddr> {
+        self.source
+    }
+    /// Address the original client connected to
+    pub fn destination(&self) -> Option<SocketAddr> {
+        self.destination
+    }
+    /// Value of the first TLV of `kind` ( v2 only ), e.g. `0x01` for ALPN
+    /// or `0x05` for the unique ID of the connection
+    pub fn tlv(&self, kind: u8) -> Option<&[u8]> {
+        self.tlvs.iter().find(|(k, _)| *k == kind).map(|(_, v)| &**v)
+    }
+    /// All TLVs ( v2 only ) in the order sent
+    pub fn tlvs(&self) -> impl Iterator<Item = (u8, &[u8])> {
+        self.tlvs.iter().map(|(k, v)| (*k, &**v))
+    }
+}
+
+impl<'req> crate::FromRequest<'req> for &'req ProxyInfo {
+    type Error = std::convert::Infallible;
+    #[inline]
+    fn from_request(req: &'req crate::Request) -> Option<Result<Self, Self::Error>> {
+        req.proxy().map(Ok)
+    }
+}
+
+/// Notification of the client's disconnection while its request is being handled
+///
+/// Available in handlers as `Disconnected`, or by `Request::disconnected` in fangs.
+/// `.await` it to complete when the client has gone, for example to stop expensive
+/// work spawned for the request ( see also `ServerConfig::cancel_on_disconnect` ).
+///
+/// Only HTTP/1.1 connections are watched, where the client closing the connection
+/// after the request ( including a half-close ) or resetting it is noticed;
+/// otherwise, including on HTTP/2, this never completes.
+///
+/// *example.rs*
+/// ```no_run
+/// use ohkami::prelude::*;
+/// use ohkami::Disconnected;
+///
+/// #[derive(Clone)]
+/// struct LogDisconnection;
+/// impl FangAction for LogDisconnection {
+///     async fn fore<'a>(&'a self, req: &'a mut Request) -> Result<(), Response> {
+///         let (disconnected, path) = (req.disconnected(), req.path.str().into_owned());
+///         tokio::spawn(async move {
+///             disconnected.await;
+///             println!("client has gone while/after requesting {path}");
+///         });
+///         Ok(())
+///     }
+/// }
+///
+/// async fn report(disconnected: Disconnected) -> String {
+///     let work = tokio::spawn(async {
+///         /* expensive work */
+///         String::from("report")
+///     });
+///     let abort = work.abort_handle();
+///     tokio::spawn(async move {
+///         disconnected.await;
+///         abort.abort();
+///     });
+///     work.await.unwrap_or_default()
+/// }
+///
+/// #[tokio::main]
+/// async fn main() {
+///     Ohkami::with(LogDisconnection, (
+///         "/report".GET(report),
+///     )).howl("localhost:8000").await
+/// }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Disconnected(pub(crate) Option<ohkami_lib::signal::watch::Receiver<bool>>);
+
+impl Disconnected {
+    /// Whether the client has already gone
+    pub fn is_disconnected(&self) -> bool {
+        self.0.as_ref().is_some_and(|d| *d.borrow() || d.has_changed().is_err())
+    }
+}
+
+impl std::future::IntoFuture for Disconnected {
+    type Output     = ();
+    type IntoFuture = std::pin::Pin<Box<dyn std::future::Future<Output = ()> + Send>>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        Box::pin(async move {
+            match self.0 {
+                /* `Err` when the connection has been closed */
+                Some(mut d) => {let _ = d.wait_for(|d| *d).await;}
+                None => std::future::pending().await
+            }
+        })
+    }
+}
+
+impl<'req> crate::FromRequest<'req> for Disconnected {
+    type Error = std::convert::Infallible;
+    #[inline]
+    fn from_request(req: &'req crate::Request) -> Option<Result<Self, Self::Error>> {
+        Some(Ok(req.disconnected()))
+    }
+}
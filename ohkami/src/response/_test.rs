@@ -7,7 +7,7 @@ macro_rules! assert_bytes_eq {
     ($res:expr, $expected:expr) => {
         {
             let mut res_bytes = Vec::new();
-            $res.send(&mut res_bytes).await;
+            $res.send(&mut res_bytes).await.unwrap();
 
             if res_bytes != $expected {
                 panic!("\n\
@@ -213,3 +213,45 @@ async fn test_stream_response() {
         \r\n\
     ").into_bytes());
 }
+
+#[crate::__rt__::test] async fn test_response_write_timeout() {
+    use std::{io, pin::Pin, task::{Context, Poll}, time::Duration};
+
+    /// In-memory connection whose peer never reads
+    struct Stalled;
+
+    impl super::Destination for Stalled {
+        fn write_timeout(&self) -> Option<Duration> {
+            Some(Duration::from_millis(100))
+        }
+    }
+
+    #[cfg(feature="rt_tokio")]
+    impl tokio::io::AsyncWrite for Stalled {
+        fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, _: &[u8]) -> Poll<io::Result<usize>> {Poll::Pending}
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {Poll::Pending}
+        fn poll_shutdown(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {Poll::Pending}
+    }
+    #[cfg(feature="rt_async-std")]
+    impl futures_util::io::AsyncWrite for Stalled {
+        fn poll_write(self: Pin<&mut Self>, _: &mut Context<'_>, _: &[u8]) -> Poll<io::Result<usize>> {Poll::Pending}
+        fn poll_flush(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {Poll::Pending}
+        fn poll_close(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<io::Result<()>> {Poll::Pending}
+    }
+
+    let sent = crate::util::timeout_in(Duration::from_secs(1),
+        Response::OK().with_text("Hello, world!").send(&mut Stalled)
+    ).await.expect("sending should give up by `write_timeout`");
+    assert!(matches!(sent, Err(e) if e.kind() == io::ErrorKind::TimedOut));
+}
+
+#[test]
+#[allow(deprecated)]
+fn test_exception_failed_alias() {
+    use crate::{Status, IntoResponse, typed};
+
+    assert_eq!(Status::ExceptionFailed, Status::ExpectationFailed);
+    assert_eq!(Response::ExceptionFailed().status, Status::ExpectationFailed);
+    let typed: typed::status::ExceptionFailed<&str> = typed::status::ExceptionFailed("expected");
+    assert_eq!(typed.into_response().status, Status::ExpectationFailed);
+}
@@ -154,6 +154,22 @@ impl Response {
     }
 }
 
+/// Format a chunk of `Content::Stream` as a server-sent event
+#[cfg(all(feature="sse", feature="__rt_native__"))]
+pub(crate) fn sse_message(chunk: &str) -> Vec<u8> {
+    let mut message = Vec::with_capacity(
+        /* capacity for a single line */
+        "data: ".len() + chunk.len() + "\n\n".len()
+    );
+    for line in chunk.split('\n') {
+        message.extend_from_slice(b"data: ");
+        message.extend_from_slice(line.as_bytes());
+        message.push(b'\n');
+    }
+    message.push(b'\n');
+    message
+}
+
 #[cfg(feature="__rt_native__")]
 pub(super) enum Upgrade {
     None,
@@ -168,12 +184,59 @@ impl Upgrade {
         matches!(self, Self::None)
     }
 }
+
+/// Where `Response::send` writes to
+#[cfg(feature="__rt_native__")]
+pub(crate) trait Destination: AsyncWriter + Unpin {
+    /// Completes when the peer has gone, to stop streaming to nobody
+    #[cfg_attr(not(feature="sse"), allow(unused))]
+    fn gone(&mut self) -> impl std::future::Future<Output = ()> + '_ {
+        std::future::pending()
+    }
+
+    /// Deadline for each write, beyond which the peer is taken as not reading
+    fn write_timeout(&self) -> Option<std::time::Duration> {
+        None
+    }
+}
+
+/// Write and flush `bytes` within `conn.write_timeout()`
+#[cfg(feature="__rt_native__")]
+async fn write(conn: &mut impl Destination, bytes: &[u8]) -> std::io::Result<()> {
+    let timeout = conn.write_timeout();
+    let write = async {
+        conn.write_all(bytes).await?;
+        conn.flush().await
+    };
+    match timeout {
+        None          => write.await,
+        Some(timeout) => crate::util::timeout_in(timeout, write).await.unwrap_or_else(|| Err(
+            std::io::Error::new(std::io::ErrorKind::TimedOut, "Timed out writing a response")
+        )),
+    }
+}
+
+#[cfg(all(test, feature="__rt_native__"))]
+impl Destination for Vec<u8> {}
 #[cfg(feature="__rt_native__")]
 impl Response {
+    /// Adjust to an HTTP/1.0 client, which doesn't know `Transfer-Encoding: chunked`.
+    /// Returns `true` when the body is delimited by closing the connection.
+    pub(crate) fn downgrade_to_http10(&mut self) -> bool {
+        match &self.content {
+            #[cfg(feature="sse")]
+            Content::Stream(_) => {
+                self.headers.set().TransferEncoding(None);
+                true
+            }
+            _ => false
+        }
+    }
+
     #[cfg_attr(not(feature="sse"), inline)]
     pub(crate) async fn send(mut self,
-        conn: &mut (impl AsyncWriter + Unpin)
-    ) -> Upgrade {
+        conn: &mut impl Destination
+    ) -> std::io::Result<Upgrade> {
         self.complete();
 
         match self.content {
@@ -185,10 +248,9 @@ impl Response {
                     crate::push_unchecked!(buf <- self.status.line());
                     self.headers.write_unchecked_to(&mut buf);
                 }
-                conn.write_all(&buf).await.expect("Failed to send response");
-                conn.flush().await.expect("Failed to flush connection");
+                write(conn, &buf).await?;
 
-                Upgrade::None
+                Ok(Upgrade::None)
             }
 
             Content::Payload(bytes) => {
@@ -201,14 +263,16 @@ impl Response {
                     self.headers.write_unchecked_to(&mut buf);
                     crate::push_unchecked!(buf <- bytes);
                 }
-                conn.write_all(&buf).await.expect("Failed to send response");
-                conn.flush().await.expect("Failed to flush connection");
+                write(conn, &buf).await?;
 
-                Upgrade::None
+                Ok(Upgrade::None)
             }
 
             #[cfg(feature="sse")]
             Content::Stream(mut stream) => {
+                /* not chunked for HTTP/1.0 ( see `downgrade_to_http10` ) */
+                let chunked = self.headers.TransferEncoding().is_some();
+
                 let mut buf = Vec::<u8>::with_capacity(
                     self.status.line().len() +
                     self.headers.size
@@ -216,26 +280,26 @@ impl Response {
                     crate::push_unchecked!(buf <- self.status.line());
                     self.headers.write_unchecked_to(&mut buf);
                 }
-                conn.write_all(&buf).await.expect("Failed to send response");
-                conn.flush().await.expect("Failed to flush connection");
-
-                while let Some(chunk) = stream.next().await {
+                write(conn, &buf).await?;
+
+                loop {
+                    let Some(chunk) = crate::util::until(conn.gone(), stream.next()).await else {
+                        crate::DEBUG!("Peer has gone while streaming");
+                        return Ok(Upgrade::None)
+                    };
+                    let Some(chunk) = chunk else {break};
                     match chunk {
                         Err(msg)  => {
                             crate::warning!("Error in stream: {msg}");
                             break
                         }
                         Ok(chunk) => {
-                            let mut message = Vec::with_capacity(
-                                /* capacity for a single line */
-                                "data: ".len() + chunk.len() + "\n\n".len()
-                            );
-                            for line in chunk.split('\n') {
-                                message.extend_from_slice(b"data: ");
-                                message.extend_from_slice(line.as_bytes());
-                                message.push(b'\n');
+                            let mut message = sse_message(&chunk);
+
+                            if !chunked {
+                                write(conn, &message).await?;
+                                continue
                             }
-                            message.push(b'\n');
 
                             let size_hex_bytes = ohkami_lib::num::hexized_bytes(message.len());
 
@@ -247,15 +311,15 @@ impl Response {
                             #[cfg(feature="DEBUG")]
                             println!("\n[sending chunk]\n{}", chunk.escape_ascii());
 
-                            conn.write_all(&chunk).await.expect("Failed to send response");
-                            conn.flush().await.expect("Failed to flush connection");
+                            write(conn, &chunk).await?;
                         }
                     }
                 }
-                conn.write_all(b"0\r\n\r\n").await.expect("Failed to send response");
-                conn.flush().await.expect("Failed to flush connection");
+                if chunked {
+                    write(conn, b"0\r\n\r\n").await?;
+                }
 
-                Upgrade::None
+                Ok(Upgrade::None)
             }
 
             #[cfg(all(feature="ws", feature="__rt_native__"))]
@@ -267,10 +331,9 @@ impl Response {
                     crate::push_unchecked!(buf <- self.status.line());
                     self.headers.write_unchecked_to(&mut buf);
                 }
-                conn.write_all(&buf).await.expect("Failed to send response");
-                conn.flush().await.expect("Failed to flush connection");
+                write(conn, &buf).await?;
 
-                Upgrade::WebSocket((config, handler))
+                Ok(Upgrade::WebSocket((config, handler)))
             }
         }
     }
@@ -87,7 +87,7 @@ macro_rules! status {
     414 URITooLong                    : "414 URI Too Long",
     415 UnsupportedMediaType          : "415 Unsupported Media Type",
     416 RangeNotSatisfiable           : "416 Range Not Satisfiable",
-    417 ExceptionFailed               : "417 Exception Failed",
+    417 ExpectationFailed             : "417 Expectation Failed",
     418 Im_a_teapot                   : "418 I'm a teapot",
     421 MisdirectedRequest            : "421 Misdirected Request",
     422 UnprocessableEntity           : "422 Unprocessable Entity",
@@ -112,6 +112,20 @@ macro_rules! status {
     511 NetworkAuthenticationRequired : "511 Network Authentication Required",
 }
 
+#[allow(non_upper_case_globals)]
+impl Status {
+    #[deprecated = "Use `Status::ExpectationFailed` instead"]
+    pub const ExceptionFailed: Self = Self::ExpectationFailed;
+}
+#[allow(non_snake_case)]
+impl Response {
+    #[deprecated = "Use `Response::ExpectationFailed` instead"]
+    #[inline(always)]
+    pub fn ExceptionFailed() -> Self {
+        Self::ExpectationFailed()
+    }
+}
+
 const _: () = {
     impl std::fmt::Debug for Status {
         fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -0,0 +1,196 @@
+#![cfg(feature="__rt_native__")]
+
+use std::{io, pin::Pin, task::{Context, Poll}};
+use crate::__rt__::TcpStream;
+
+
+/// An accepted connection, the underlying stream of a WebSocket connection
+pub enum Connection {
+    Tcp(TcpStream),
+
+    #[cfg(unix)]
+    Unix(crate::__rt__::UnixStream),
+
+    #[cfg(feature="tls")]
+    Tls(Box<crate::__rt__::TlsStream<TcpStream>>),
+}
+
+const _: () = {
+    impl From<TcpStream> for Connection {
+        fn from(tcp: TcpStream) -> Self {
+            Self::Tcp(tcp)
+        }
+    }
+    #[cfg(unix)]
+    impl From<crate::__rt__::UnixStream> for Connection {
+        fn from(unix: crate::__rt__::UnixStream) -> Self {
+            Self::Unix(unix)
+        }
+    }
+    #[cfg(feature="tls")]
+    impl From<crate::__rt__::TlsStream<TcpStream>> for Connection {
+        fn from(tls: crate::__rt__::TlsStream<TcpStream>) -> Self {
+            Self::Tls(Box::new(tls))
+        }
+    }
+
+    impl std::fmt::Debug for Connection {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            match self {
+                Self::Tcp(_) => f.write_str("Connection::Tcp"),
+                #[cfg(unix)]
+                Self::Unix(_) => f.write_str("Connection::Unix"),
+                #[cfg(feature="tls")]
+                Self::Tls(_) => f.write_str("Connection::Tls"),
+            }
+        }
+    }
+};
+
+impl crate::response::Destination for Connection {}
+
+/// `Connection` sending a response while watching the peer's disconnection
+#[cfg_attr(not(feature="sse"), allow(unused))]
+pub(crate) struct Watched<'c> {
+    pub(crate) connection: &'c mut Connection,
+    /// bytes sent by the peer meanwhile, the beginning of the next pipelined request
+    pub(crate) read_ahead: &'c mut Vec<u8>,
+    /// max length of `read_ahead`
+    pub(crate) limit:      usize,
+    pub(crate) disconnect: &'c ohkami_lib::signal::watch::Sender<bool>,
+    pub(crate) cancel:     bool,
+    pub(crate) timeout:    std::time::Duration,
+}
+
+impl Connection {
+    /// Completes when the peer has closed the connection ( EOF after the request
+    /// was fully read, including a half-close ) or it's broken.
+    /// When the peer sends some bytes instead, they're kept into `read_ahead`
+    /// up to `limit` bytes, and this never completes.
+    pub(crate) async fn closed(&mut self, read_ahead: &mut Vec<u8>, limit: usize) {
+        use crate::__rt__::AsyncReader;
+
+        if read_ahead.len() >= limit {
+            /* leave the rest in the connection, to be read as the next request */
+            return std::future::pending().await
+        }
+
+        let mut buf = [0; 1 << 8];
+        let len = buf.len().min(limit - read_ahead.len());
+        match self.read(&mut buf[..len]).await {
+            Ok(0) | Err(_) => (),
+            Ok(n) => {
+                read_ahead.extend_from_slice(&buf[..n]);
+                std::future::pending().await
+            }
+        }
+    }
+}
+
+impl crate::response::Destination for Watched<'_> {
+    async fn gone(&mut self) {
+        self.connection.closed(self.read_ahead, self.limit).await;
+        self.disconnect.send_replace(true);
+        if !self.cancel {std::future::pending().await}
+    }
+
+    fn write_timeout(&self) -> Option<std::time::Duration> {
+        Some(self.timeout)
+    }
+}
+
+macro_rules! dispatch {
+    ($self:ident => |$conn:ident| $proc:expr) => {
+        match $self.get_mut() {
+            Connection::Tcp($conn) => {let $conn = Pin::new($conn); $proc}
+            #[cfg(unix)]
+            Connection::Unix($conn) => {let $conn = Pin::new($conn); $proc}
+            #[cfg(feature="tls")]
+            Connection::Tls($conn) => {let $conn = Pin::new(&mut **$conn); $proc}
+        }
+    };
+}
+
+#[cfg(feature="rt_tokio")]
+const _: () = {
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+    impl AsyncRead for Connection {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+            dispatch!(self => |conn| conn.poll_read(cx, buf))
+        }
+    }
+
+    impl AsyncWrite for Connection {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            dispatch!(self => |conn| conn.poll_write(cx, buf))
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            dispatch!(self => |conn| conn.poll_flush(cx))
+        }
+        #[inline]
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            dispatch!(self => |conn| conn.poll_shutdown(cx))
+        }
+    }
+
+    impl AsyncWrite for Watched<'_> {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut *self.get_mut().connection).poll_write(cx, buf)
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut *self.get_mut().connection).poll_flush(cx)
+        }
+        #[inline]
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut *self.get_mut().connection).poll_shutdown(cx)
+        }
+    }
+};
+
+#[cfg(any(feature="rt_async-std", feature="rt_smol", feature="rt_glommio"))]
+const _: () = {
+    use futures_util::io::{AsyncRead, AsyncWrite};
+
+    impl AsyncRead for Connection {
+        #[inline]
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            dispatch!(self => |conn| conn.poll_read(cx, buf))
+        }
+    }
+
+    impl AsyncWrite for Connection {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            dispatch!(self => |conn| conn.poll_write(cx, buf))
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            dispatch!(self => |conn| conn.poll_flush(cx))
+        }
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            dispatch!(self => |conn| conn.poll_close(cx))
+        }
+    }
+
+    impl AsyncWrite for Watched<'_> {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut *self.get_mut().connection).poll_write(cx, buf)
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut *self.get_mut().connection).poll_flush(cx)
+        }
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut *self.get_mut().connection).poll_close(cx)
+        }
+    }
+};
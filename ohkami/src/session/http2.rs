@@ -0,0 +1,350 @@
+#![cfg(feature="http2")]
+
+use super::{Connection, Session};
+use std::{io, pin::Pin, sync::Arc, task::{Context, Poll}, time::Duration};
+use bytes::Bytes;
+use futures_util::stream::FuturesUnordered;
+use h2::{RecvStream, SendStream, server::SendResponse};
+use crate::__rt__::AsyncReader;
+use crate::ohkami::router::RadixRouter;
+use crate::response::Content;
+use crate::util::{timeout_in, until};
+use crate::{Request, Response, ServerConfig};
+
+
+const PREFACE: &[u8] = b"PRI * HTTP/2.0\r\n\r\nSM\r\n\r\n";
+
+pub(super) enum Protocol {
+    /// HTTP/2, with the bytes of the connection preface already read
+    Http2(Vec<u8>),
+    /// HTTP/1.x, with the bytes of the first request already read
+    Http1(Vec<u8>),
+    Closed,
+}
+
+/// Decide the protocol of the connection by ALPN for TLS,
+/// or by the HTTP/2 connection preface ( prior knowledge, RFC 9113, 3.3 ) for plain TCP
+pub(super) async fn detect(session: &mut Session) -> Protocol {
+    #[cfg(feature="tls")]
+    if let Some(tls) = &session.tls {
+        return match tls.alpn_protocol() {
+            Some(b"h2") => Protocol::Http2(Vec::new()),
+            _           => Protocol::Http1(Vec::new()),
+        }
+    }
+
+    let mut buf = vec![0; session.config.header_buffer_size.max(PREFACE.len())];
+    let mut filled = 0;
+    loop {
+        let timeout = match filled {
+            0 => session.config.keepalive_timeout,
+            _ => session.config.header_read_timeout,
+        };
+        match timeout_in(timeout, session.connection.read(&mut buf[filled..])).await {
+            Some(Ok(0)) | None => return Protocol::Closed,
+            Some(Ok(n))        => filled += n,
+            Some(Err(_e))      => {
+                crate::DEBUG!("Failed to read stream: {_e}");
+                return Protocol::Closed
+            }
+        }
+
+        let n = filled.min(PREFACE.len());
+        if buf[..n] != PREFACE[..n] {
+            buf.truncate(filled);
+            return Protocol::Http1(buf)
+        }
+        if n == PREFACE.len() {
+            buf.truncate(filled);
+            return Protocol::Http2(buf)
+        }
+    }
+}
+
+pub(super) async fn serve(session: Session, prefix: Vec<u8>) {
+    let mut shutdown = std::pin::pin!(session.shutting_down());
+
+    let Session {
+        router,
+        connection,
+        peer,
+        config,
+        shutdown: _,
+        proxy,
+        #[cfg(feature="tls")]
+        tls,
+    } = session;
+
+    let handshake = h2::server::Builder::new()
+        .max_header_list_size(config.max_header_size.try_into().unwrap_or(u32::MAX))
+        .max_concurrent_streams(config.max_concurrent_streams.try_into().unwrap_or(u32::MAX))
+        .handshake::<_, Bytes>(Rewind { prefix, connection });
+    let mut h2 = match timeout_in(config.header_read_timeout, handshake).await {
+        Some(Ok(h2)) => h2,
+        Some(Err(_e)) => {
+            crate::DEBUG!("HTTP/2 handshake failed: {_e}");
+            return
+        }
+        None => return,
+    };
+
+    crate::DEBUG!("HTTP/2 session started");
+
+    /* streams are served in this session's task, not to escape from
+       `max_connections` nor draining on shutdown */
+    let mut streams = FuturesUnordered::new();
+    let mut shutting_down = false;
+    loop {
+        let idle = streams.is_empty();
+        let next = std::future::poll_fn(|cx| {
+            if let Poll::Ready(Some(())) = futures_util::StreamExt::poll_next_unpin(&mut streams, cx) {
+                return Poll::Ready(Next::Finished)
+            }
+            h2.poll_accept(cx).map(|accepted| Next::Accepted(Box::new(accepted)))
+        });
+        let next = if shutting_down {
+            Some(next.await)
+        } else if idle {
+            until(&mut shutdown, timeout_in(config.keepalive_timeout, next)).await.flatten()
+        } else {
+            until(&mut shutdown, next).await
+        };
+
+        match next {
+            None => {
+                /* no more streams, letting in-flight ones finish */
+                h2.graceful_shutdown();
+                shutting_down = true;
+            }
+            Some(Next::Finished) => (),
+            Some(Next::Accepted(accepted)) => match *accepted {
+                None => break,
+                Some(Err(_e)) => {
+                    crate::DEBUG!("HTTP/2 connection error: {_e}");
+                    break
+                }
+                Some(Ok((request, respond))) => {
+                    streams.push(handle(request, respond, router.clone(), config.clone(), peer.clone(), proxy.clone(),
+                        #[cfg(feature="tls")] tls.clone()
+                    ));
+                }
+            }
+        }
+    }
+    while futures_util::StreamExt::next(&mut streams).await.is_some() {}
+
+    crate::DEBUG!("HTTP/2 session finished");
+}
+
+/// A new stream, the end of the connection, or an error from `h2::server::Connection::poll_accept`
+type Accepted = Option<Result<(http::Request<RecvStream>, SendResponse<Bytes>), h2::Error>>;
+
+enum Next {
+    Accepted(Box<Accepted>),
+    Finished,
+}
+
+/// Serve one stream through the same `RadixRouter::handle` as HTTP/1.1
+async fn handle(
+    request: http::Request<RecvStream>,
+    mut respond: SendResponse<Bytes>,
+    router:  Arc<RadixRouter>,
+    config:  Arc<ServerConfig>,
+    peer:    crate::PeerAddr,
+    proxy:   Option<Arc<crate::ProxyInfo>>,
+    #[cfg(feature="tls")]
+    tls:     Option<Arc<crate::tls::TlsInfo>>,
+) {
+    let mut req = Request::init(peer);
+    req.proxy = proxy;
+    #[cfg(feature="tls")] {
+        req.tls = tls;
+    }
+
+    let (head, body) = request.into_parts();
+    let res = match collect(body, &config).await {
+        Ok(payload) => match req.take_over_h2(head, payload) {
+            Ok(()) => router.handle_catching(&mut req).await,
+            Err(res) => *res,
+        },
+        Err(res) => res,
+    };
+
+    if let Err(_e) = send(res, &mut respond, config.response_write_timeout).await {
+        crate::DEBUG!("Failed to send HTTP/2 response: {_e}");
+    }
+}
+
+/// Collect the request body within `payload_limit` and `body_read_timeout`
+async fn collect(mut body: RecvStream, config: &ServerConfig) -> Result<Vec<u8>, Response> {
+    let mut payload = Vec::new();
+    if body.is_end_stream() {
+        return Ok(payload)
+    }
+
+    let collect = async {
+        while let Some(data) = body.data().await {
+            let data = data.map_err(|_e| {
+                crate::DEBUG!("Failed to read HTTP/2 body: {_e}");
+                Response::BadRequest()
+            })?;
+            let _ = body.flow_control().release_capacity(data.len());
+            if payload.len() + data.len() > config.payload_limit {
+                return Err(Response::PayloadTooLarge())
+            }
+            payload.extend_from_slice(&data);
+        }
+        Ok(())
+    };
+    match timeout_in(config.body_read_timeout, collect).await {
+        Some(collected) => collected.map(|()| payload),
+        None            => Err(Response::RequestTimeout()),
+    }
+}
+
+async fn send(mut res: Response, respond: &mut SendResponse<Bytes>, timeout: Duration) -> Result<(), h2::Error> {
+    /* connection-specific fields are prohibited in HTTP/2 ( RFC 9113, 8.2.2 ) */
+    const CONNECTION_SPECIFIC: &[&str] = &[
+        "Connection", "Keep-Alive", "Proxy-Connection", "Transfer-Encoding", "Upgrade",
+    ];
+
+    #[cfg(feature="ws")]
+    if matches!(res.content, Content::WebSocket(_)) {
+        /* WebSocket over HTTP/2 ( RFC 8441 ) is not supported */
+        res = Response::NotImplemented();
+    }
+
+    res.complete();
+
+    let mut head = http::Response::builder().status(res.status.code());
+    for (name, value) in res.headers.iter() {
+        if CONNECTION_SPECIFIC.iter().any(|h| name.eq_ignore_ascii_case(h)) {continue}
+        head = head.header(name, value);
+    }
+    let head = match head.body(()) {
+        Ok(head) => head,
+        Err(e) => {
+            crate::warning!("Invalid response for HTTP/2: {e}");
+            respond.send_reset(h2::Reason::INTERNAL_ERROR);
+            return Ok(())
+        }
+    };
+
+    match res.content {
+        Content::None => {
+            respond.send_response(head, true)?;
+        }
+
+        Content::Payload(bytes) => {
+            let mut stream = respond.send_response(head, bytes.is_empty())?;
+            if !bytes.is_empty() {
+                send_data(&mut stream, Bytes::copy_from_slice(&bytes), true, timeout).await?;
+            }
+        }
+
+        #[cfg(feature="sse")]
+        Content::Stream(mut stream) => {
+            use crate::util::StreamExt;
+
+            let mut send_stream = respond.send_response(head, false)?;
+            while let Some(chunk) = stream.next().await {
+                match chunk {
+                    Err(msg)  => {
+                        crate::warning!("Error in stream: {msg}");
+                        break
+                    }
+                    Ok(chunk) => {
+                        let message = crate::response::sse_message(&chunk);
+                        send_data(&mut send_stream, message.into(), false, timeout).await?;
+                    }
+                }
+            }
+            send_stream.send_data(Bytes::new(), true)?;
+        }
+
+        #[cfg(feature="ws")]
+        Content::WebSocket(_) => unreachable!("replaced with `501 Not Implemented`"),
+    }
+
+    Ok(())
+}
+
+/// Send `data` as soon as the peer's flow control window allows,
+/// resetting the stream if it's not done within `timeout` ( the peer isn't reading )
+async fn send_data(stream: &mut SendStream<Bytes>, mut data: Bytes, end_of_stream: bool, timeout: Duration) -> Result<(), h2::Error> {
+    let send = async {
+        stream.reserve_capacity(data.len());
+        while !data.is_empty() {
+            let capacity = match std::future::poll_fn(|cx| stream.poll_capacity(cx)).await {
+                Some(capacity) => capacity?,
+                None           => return Ok(()), /* reset by the peer */
+            };
+            let chunk = data.split_to(capacity.min(data.len()));
+            stream.send_data(chunk, end_of_stream && data.is_empty())?;
+        }
+        Ok(())
+    };
+    match timeout_in(timeout, send).await {
+        Some(sent) => sent,
+        None => {
+            stream.send_reset(h2::Reason::CANCEL);
+            Err(h2::Reason::CANCEL.into())
+        }
+    }
+}
+
+/// `Connection` with the bytes already read while detecting the protocol put back,
+/// as `tokio::io` traits `h2` requires
+struct Rewind {
+    prefix:     Vec<u8>,
+    connection: Connection,
+}
+
+impl tokio::io::AsyncRead for Rewind {
+    fn poll_read(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut tokio::io::ReadBuf<'_>) -> Poll<io::Result<()>> {
+        if !self.prefix.is_empty() {
+            let n = self.prefix.len().min(buf.remaining());
+            buf.put_slice(&self.prefix[..n]);
+            self.prefix.drain(..n);
+            return Poll::Ready(Ok(()))
+        }
+
+        #[cfg(feature="rt_tokio")] {
+            Pin::new(&mut self.connection).poll_read(cx, buf)
+        }
+        #[cfg(any(feature="rt_async-std", feature="rt_smol", feature="rt_glommio"))] {
+            let n = std::task::ready!(futures_util::io::AsyncRead::poll_read(
+                Pin::new(&mut self.connection), cx, buf.initialize_unfilled()
+            ))?;
+            buf.advance(n);
+            Poll::Ready(Ok(()))
+        }
+    }
+}
+
+impl tokio::io::AsyncWrite for Rewind {
+    fn poll_write(mut self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+        #[cfg(feature="rt_tokio")] {
+            Pin::new(&mut self.connection).poll_write(cx, buf)
+        }
+        #[cfg(any(feature="rt_async-std", feature="rt_smol", feature="rt_glommio"))] {
+            futures_util::io::AsyncWrite::poll_write(Pin::new(&mut self.connection), cx, buf)
+        }
+    }
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        #[cfg(feature="rt_tokio")] {
+            Pin::new(&mut self.connection).poll_flush(cx)
+        }
+        #[cfg(any(feature="rt_async-std", feature="rt_smol", feature="rt_glommio"))] {
+            futures_util::io::AsyncWrite::poll_flush(Pin::new(&mut self.connection), cx)
+        }
+    }
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        #[cfg(feature="rt_tokio")] {
+            Pin::new(&mut self.connection).poll_shutdown(cx)
+        }
+        #[cfg(any(feature="rt_async-std", feature="rt_smol", feature="rt_glommio"))] {
+            futures_util::io::AsyncWrite::poll_close(Pin::new(&mut self.connection), cx)
+        }
+    }
+}
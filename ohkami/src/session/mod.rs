@@ -1,114 +1,254 @@
 #![cfg(feature="__rt_native__")]
 
-use std::{any::Any, pin::Pin, sync::Arc, time::Duration};
-use std::panic::{AssertUnwindSafe, catch_unwind};
-use crate::__rt__::TcpStream;
+mod connection;
+pub use connection::Connection;
+pub(crate) use connection::Watched;
+
+#[cfg(feature="http2")]
+mod http2;
+
+pub(crate) mod proxy;
+
+use std::{pin::Pin, sync::Arc};
+use ohkami_lib::signal::watch;
+use crate::__rt__::AsyncWriter;
+use crate::request::{Framing, ReadError};
 use crate::response::Upgrade;
-use crate::util::timeout_in;
 use crate::ohkami::router::RadixRouter;
-use crate::{Request, Response};
+use crate::{Request, Response, ServerConfig};
 
 
-mod env {
-    #![allow(unused, non_snake_case)]
-
-    use std::sync::OnceLock;
+/// Whether `err` just means the peer has gone, or stopped reading ( `response_write_timeout` )
+fn is_disconnection(err: &std::io::Error) -> bool {
+    use std::io::ErrorKind::*;
+    matches!(err.kind(), ConnectionReset | ConnectionAborted | BrokenPipe | UnexpectedEof | TimedOut)
+}
 
-    pub(crate) fn OHKAMI_KEEPALIVE_TIMEOUT() -> u64 {
-        static OHKAMI_KEEPALIVE_TIMEOUT: OnceLock<u64> = OnceLock::new();
-        *OHKAMI_KEEPALIVE_TIMEOUT.get_or_init(|| {
-            std::env::var("OHKAMI_KEEPALIVE_TIMEOUT").ok()
-                .map(|v| v.parse().ok()).flatten()
-                .unwrap_or(42)
-        })
+/// Close quietly on the peer's disconnection, or report `err` by `ServerConfig::on_io_error`
+fn io_error(config: &ServerConfig, peer: &crate::PeerAddr, err: std::io::Error) {
+    if is_disconnection(&err) {
+        crate::DEBUG!("Peer {peer} disconnected: {err}");
+    } else if let Some(hook) = &config.io_error_hook {
+        hook(&err, peer)
+    } else {
+        crate::warning!("[WARNING] I/O error on connection with {peer}: {err}")
     }
+}
 
-    #[cfg(feature="ws")]
-    pub(crate) fn OHKAMI_WEBSOCKET_TIMEOUT() -> u64 {
-        static OHKAMI_WEBSOCKET_TIMEOUT: OnceLock<u64> = OnceLock::new();
-        *OHKAMI_WEBSOCKET_TIMEOUT.get_or_init(|| {
-            std::env::var("OHKAMI_WEBSOCKET_TIMEOUT").ok()
-                .map(|v| v.parse().ok()).flatten()
-                .unwrap_or(1 * 60 * 60)
-        })
-    }
+/// Phase of the server's shutdown, notified to every session
+#[derive(Clone, Copy, PartialEq)]
+pub(crate) enum Shutdown {
+    None,
+    /// not accepting any more requests, closing connections after in-flight ones
+    Graceful,
+    /// `shutdown_timeout` has passed, closing connections immediately
+    Forced,
 }
 
 pub(crate) struct Session {
     router:     Arc<RadixRouter>,
-    connection: TcpStream,
-    ip:         std::net::IpAddr,
+    connection: Connection,
+    peer:       crate::PeerAddr,
+    config:     Arc<ServerConfig>,
+    shutdown:   watch::Receiver<Shutdown>,
+    proxy:      Option<Arc<crate::ProxyInfo>>,
+
+    #[cfg(feature="tls")]
+    tls:        Option<Arc<crate::tls::TlsInfo>>,
 }
 impl Session {
     pub(crate) fn new(
         router:     Arc<RadixRouter>,
-        connection: TcpStream,
-        ip:         std::net::IpAddr
+        connection: impl Into<Connection>,
+        peer:       crate::PeerAddr,
+        config:     Arc<ServerConfig>,
+        shutdown:   watch::Receiver<Shutdown>,
+        proxy:      Option<Arc<crate::ProxyInfo>>,
     ) -> Self {
+        let connection = connection.into();
+
+        #[cfg(feature="tls")]
+        let tls = match &connection {
+            Connection::Tls(tls) => Some(Arc::new(crate::tls::TlsInfo::of(tls.get_ref().1))),
+            _ => None
+        };
+
         Self {
             router,
             connection,
-            ip
+            peer,
+            config,
+            shutdown,
+            proxy,
+
+            #[cfg(feature="tls")]
+            tls,
         }
     }
 
-    pub(crate) async fn manage(mut self) {
-        #[cold] #[inline(never)]
-        fn panicking(panic: Box<dyn Any + Send>) -> Response {
-            if let Some(msg) = panic.downcast_ref::<String>() {
-                crate::warning!("[Panicked]: {msg}");
-            } else if let Some(msg) = panic.downcast_ref::<&str>() {
-                crate::warning!("[Panicked]: {msg}");
-            } else {
-                crate::warning!("[Panicked]");
-            }
-            crate::Response::InternalServerError()
+    fn is_shutting_down(&self) -> bool {
+        *self.shutdown.borrow() != Shutdown::None
+    }
+
+    /// Completes when the server starts shutting down
+    fn shutting_down(&self) -> impl std::future::Future<Output = ()> {
+        let mut shutdown = self.shutdown.clone();
+        async move {
+            let _ = shutdown.wait_for(|s| *s != Shutdown::None).await;
         }
+    }
 
-        match timeout_in(Duration::from_secs(env::OHKAMI_KEEPALIVE_TIMEOUT()), async {
-            let mut req = Request::init(self.ip);
+    /// Respond to `Expect: 100-continue` before reading the body ( RFC 9110, 10.1.1 ),
+    /// or reject the request without reading it
+    async fn expect_continue(&mut self, req: &mut Request, framing: &Framing) -> Result<(), ReadError> {
+        let Some(expect) = req.headers.Expect() else {
+            return Ok(())
+        };
+        if req.is_http10() {
+            /* HTTP/1.0 client doesn't know `100 Continue` ( RFC 9110, 10.1.1 ) */
+            return Ok(())
+        }
+        if !expect.eq_ignore_ascii_case("100-continue") {
+            return Err(Response::ExpectationFailed().into())
+        }
+        match framing {
+            Framing::None | Framing::Length(0) => return Ok(()),
+            Framing::Length(n) if *n > self.config.payload_limit => return Err(Response::PayloadTooLarge().into()),
+            _ => ()
+        }
+        /* before the body is read, so extension methods are resolved here */
+        self.router.resolve_method(req)?;
+        if !self.router.routes(req.method, &req.path) {
+            return Err(Response::ExpectationFailed().into())
+        }
+
+        let interim = [crate::Status::Continue.line(), b"\r\n"].concat();
+        self.connection.write_all(&interim).await?;
+        self.connection.flush().await?;
+        Ok(())
+    }
+
+    pub(crate) async fn manage(mut self) {
+        #[cfg(feature="http2")]
+        let prefix = match http2::detect(&mut self).await {
+            http2::Protocol::Http2(prefix) => return http2::serve(self, prefix).await,
+            http2::Protocol::Http1(prefix) => prefix,
+            http2::Protocol::Closed        => return,
+        };
+
+        let upgrade = {
+            let mut req = Request::init(self.peer.clone());
+            #[cfg(feature="http2")] {
+                req.prefill(prefix, &self.config);
+            }
+            req.proxy = self.proxy.clone();
+            #[cfg(feature="tls")] {
+                req.tls = self.tls.clone();
+            }
+            let (disconnect, disconnected) = watch::channel(false);
+            req.disconnected = crate::Disconnected(Some(disconnected));
+            let mut read_ahead = Vec::new();
             let mut req = unsafe {Pin::new_unchecked(&mut req)};
             loop {
+                if self.is_shutting_down() {break Upgrade::None}
+
                 req.clear();
-                match req.as_mut().read(&mut self.connection).await {
+                let read = async {
+                    let shutting_down = self.shutting_down();
+                    let Some(framing) = req.as_mut().read_head(&mut self.connection, &self.config, shutting_down).await? else {
+                        return Ok(None)
+                    };
+                    self.expect_continue(req.as_mut().get_mut(), &framing).await?;
+                    req.as_mut().read_body(&mut self.connection, &self.config, framing).await.map(Some)
+                }.await;
+                match read {
                     Ok(Some(())) => {
-                        let close = matches!(req.headers.Connection(), Some("close" | "Close"));
-
-                        let res = match catch_unwind(AssertUnwindSafe({
-                            let req = req.as_mut();
-                            || self.router.handle(req.get_mut())
-                        })) {
-                            Ok(future) => future.await,
-                            Err(panic) => panicking(panic),
+                        let http10 = req.is_http10();
+                        let mut close = {
+                            let connection = |option: &str| req.headers.Connection().is_some_and(|v| v
+                                .split(',').any(|token| token.trim().eq_ignore_ascii_case(option))
+                            );
+                            /* persistent by default since HTTP/1.1 ( RFC 9112, 9.3 ) */
+                            if http10 {!connection("keep-alive")} else {connection("close")}
+                        };
+
+                        let Self { router, connection, config, .. } = &mut self;
+                        let handled = crate::util::until(
+                            async {
+                                connection.closed(&mut read_ahead, config.max_header_size).await;
+                                disconnect.send_replace(true);
+                                if !config.cancel_on_disconnect {std::future::pending().await}
+                            },
+                            router.handle_catching(req.as_mut().get_mut())
+                        ).await;
+                        let Some(mut res) = handled else {
+                            crate::DEBUG!("Peer {} disconnected, cancelled handling", self.peer);
+                            break Upgrade::None
+                        };
+                        close |= self.is_shutting_down();
+                        if http10 {
+                            close |= res.downgrade_to_http10();
+                            res.headers.set().Connection(if close {"close"} else {"keep-alive"});
+                        } else if close {
+                            res.headers.set().Connection("close");
+                        }
+                        let upgrade = match res.send(&mut Watched {
+                            connection: &mut self.connection,
+                            read_ahead: &mut read_ahead,
+                            limit:      self.config.max_header_size,
+                            disconnect: &disconnect,
+                            cancel:     self.config.cancel_on_disconnect,
+                            timeout:    self.config.response_write_timeout,
+                        }).await {
+                            Ok(upgrade) => upgrade,
+                            Err(e) => {
+                                io_error(&self.config, &self.peer, e);
+                                break Upgrade::None
+                            }
                         };
-                        let upgrade = res.send(&mut self.connection).await;
 
                         if !upgrade.is_none() {break upgrade}
-                        if close {break Upgrade::None}
+                        if close || *disconnect.borrow() {break Upgrade::None}
+                        if !read_ahead.is_empty() {
+                            req.read_ahead(&read_ahead);
+                            read_ahead.clear();
+                        }
                     }
                     Ok(None) => break Upgrade::None,
-                    Err(res) => {res.send(&mut self.connection).await;},
+                    Err(ReadError::Respond(res)) => {
+                        /* the rest of the stream can't be trusted as a request */
+                        if let Err(e) = res.send(&mut self.connection).await {
+                            io_error(&self.config, &self.peer, e);
+                        }
+                        break Upgrade::None
+                    }
+                    Err(ReadError::Io(e)) => {
+                        io_error(&self.config, &self.peer, e);
+                        break Upgrade::None
+                    }
                 }
             }
-        }).await {
-            Some(Upgrade::None) | None => {
+        };
+
+        match upgrade {
+            Upgrade::None => {
                 crate::DEBUG!("about to shutdown connection");
             }
 
             #[cfg(feature="ws")]
-            Some(Upgrade::WebSocket((config, handler))) => {
+            Upgrade::WebSocket((config, handler)) => {
                 use crate::ws::{Connection, Message, CloseFrame, CloseCode};
 
                 crate::DEBUG!("WebSocket session started");
 
                 let mut conn = Connection::new(self.connection, config);
 
-                let close = timeout_in(Duration::from_secs(env::OHKAMI_WEBSOCKET_TIMEOUT()),
+                let close = crate::util::timeout_in(self.config.websocket_timeout,
                     handler(conn.clone())
                 ).await;
 
                 if !conn.is_closed() {
-                    conn.send(Message::Close(Some(match close {
+                    let closed = conn.send(Message::Close(Some(match close {
                         Some(_) => {
                             crate::DEBUG!("Closing WebSocket session...");
                             CloseFrame {
@@ -117,13 +257,16 @@ impl Session {
                             }
                         }
                         None => {
-                            crate::warning!("[WARNING] WebSocket session is aborted by `OHKAMI_WEBSOCKET_TIMEOUT` (default to 1 hour, and can be set via environment variable)");
+                            crate::warning!("[WARNING] WebSocket session is aborted by `websocket_timeout` (default to 1 hour, and can be set via `ServerConfig`)");
                             CloseFrame {
                                 code:   CloseCode::Library(4000),
                                 reason: Some("OHKAMI_WEBSOCKET_TIMEOUT".into())
                             }
                         }
-                    }))).await.expect("Failed to send close message");
+                    }))).await;
+                    if let Err(e) = closed {
+                        io_error(&self.config, &self.peer, e);
+                    }
                 }
 
                 crate::DEBUG!("WebSocket session finished");
@@ -0,0 +1,150 @@
+#![cfg(feature="__rt_native__")]
+
+//! PROXY protocol v1 / v2 header at the start of a connection
+//! ( https://www.haproxy.org/download/2.9/doc/proxy-protocol.txt )
+
+use std::io;
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr};
+use crate::__rt__::AsyncReader;
+use crate::request::ProxyInfo;
+
+
+const V2_SIGNATURE: [u8; 12] = *b"\r\n\r\n\0\r\nQUIT\n";
+
+/// including the trailing CRLF
+const V1_MAX_LENGTH: usize = 107;
+
+fn invalid(msg: &'static str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, msg)
+}
+
+/// Read exactly the PROXY header, leaving the following bytes in `conn`
+pub(crate) async fn read(conn: &mut (impl AsyncReader + Unpin)) -> io::Result<ProxyInfo> {
+    /* shorter than any v1 header ( `PROXY UNKNOWN\r\n` ) */
+    let mut head = [0; V2_SIGNATURE.len()];
+    conn.read_exact(&mut head).await?;
+
+    if head == V2_SIGNATURE {
+        let mut fixed = [0; 4];
+        conn.read_exact(&mut fixed).await?;
+        let [version_command, family, len @ ..] = fixed;
+
+        let mut payload = vec![0; u16::from_be_bytes(len) as usize];
+        conn.read_exact(&mut payload).await?;
+
+        parse_v2(version_command, family, &payload)
+
+    } else if head.starts_with(b"PROXY ") {
+        let mut line = head.to_vec();
+        while !line.ends_with(b"\r\n") {
+            if line.len() >= V1_MAX_LENGTH {
+                return Err(invalid("PROXY v1 header is too long"))
+            }
+            let mut byte = [0];
+            conn.read_exact(&mut byte).await?;
+            line.push(byte[0]);
+        }
+
+        parse_v1(&line)
+
+    } else {
+        Err(invalid("Connection doesn't start with PROXY header"))
+    }
+}
+
+/// `PROXY TCP4 192.0.2.1 192.0.2.2 56324 443\r\n`
+pub(crate) fn parse_v1(line: &[u8]) -> io::Result<ProxyInfo> {
+    let line = std::str::from_utf8(line).ok()
+        .and_then(|line| line.strip_prefix("PROXY "))
+        .and_then(|line| line.strip_suffix("\r\n"))
+        .ok_or_else(|| invalid("Invalid PROXY v1 header"))?;
+
+    let mut parts = line.split(' ');
+    match parts.next() {
+        /* the rest is to be ignored */
+        Some("UNKNOWN") => Ok(ProxyInfo { source: None, destination: None, tlvs: Vec::new() }),
+
+        Some(family @ ("TCP4" | "TCP6")) => {
+            let (Some(src), Some(dst), Some(src_port), Some(dst_port), None) = (
+                parts.next(), parts.next(), parts.next(), parts.next(), parts.next()
+            ) else {
+                return Err(invalid("Invalid PROXY v1 header"))
+            };
+
+            let addr = |ip: &str, port: &str| -> Option<SocketAddr> {
+                let ip = match family {
+                    "TCP4" => IpAddr::V4(ip.parse().ok()?),
+                    _      => IpAddr::V6(ip.parse().ok()?),
+                };
+                /* no leading `+` or zeros */
+                if port.starts_with(['+', '0']) && port != "0" {return None}
+                Some(SocketAddr::new(ip, port.parse().ok()?))
+            };
+
+            Ok(ProxyInfo {
+                source:      Some(addr(src, src_port).ok_or_else(|| invalid("Invalid source in PROXY v1 header"))?),
+                destination: Some(addr(dst, dst_port).ok_or_else(|| invalid("Invalid destination in PROXY v1 header"))?),
+                tlvs:        Vec::new(),
+            })
+        }
+
+        _ => Err(invalid("Unknown protocol in PROXY v1 header")),
+    }
+}
+
+/// `payload` is the addresses and TLVs following the 16-byte fixed part
+pub(crate) fn parse_v2(version_command: u8, family: u8, payload: &[u8]) -> io::Result<ProxyInfo> {
+    if version_command >> 4 != 0x2 {
+        return Err(invalid("Unsupported PROXY v2 version"))
+    }
+
+    let local = match version_command & 0x0F {
+        0x0 => true,
+        0x1 => false,
+        _   => return Err(invalid("Unknown PROXY v2 command")),
+    };
+
+    let (addrs, tlvs) = match family >> 4 {
+        0x0 /* AF_UNSPEC */ => (None, payload),
+        0x1 /* AF_INET   */ => {
+            let (addrs, tlvs) = payload.split_at_checked(12).ok_or_else(|| invalid("Truncated PROXY v2 addresses"))?;
+            let ip = |at: usize| IpAddr::V4(Ipv4Addr::from(<[u8; 4]>::try_from(&addrs[at..at+4]).unwrap()));
+            let port = |at: usize| u16::from_be_bytes([addrs[at], addrs[at+1]]);
+            (Some((SocketAddr::new(ip(0), port(8)), SocketAddr::new(ip(4), port(10)))), tlvs)
+        }
+        0x2 /* AF_INET6  */ => {
+            let (addrs, tlvs) = payload.split_at_checked(36).ok_or_else(|| invalid("Truncated PROXY v2 addresses"))?;
+            let ip = |at: usize| IpAddr::V6(Ipv6Addr::from(<[u8; 16]>::try_from(&addrs[at..at+16]).unwrap()));
+            let port = |at: usize| u16::from_be_bytes([addrs[at], addrs[at+1]]);
+            (Some((SocketAddr::new(ip(0), port(32)), SocketAddr::new(ip(16), port(34)))), tlvs)
+        }
+        0x3 /* AF_UNIX   */ => {
+            let (_, tlvs) = payload.split_at_checked(216).ok_or_else(|| invalid("Truncated PROXY v2 addresses"))?;
+            (None, tlvs)
+        }
+        _ => return Err(invalid("Unknown PROXY v2 address family")),
+    };
+
+    /* the connection is from the proxy itself, e.g. health check */
+    if local {
+        return Ok(ProxyInfo { source: None, destination: None, tlvs: Vec::new() })
+    }
+
+    let mut tlvs = tlvs;
+    let mut parsed = Vec::new();
+    while !tlvs.is_empty() {
+        let [kind, len_hi, len_lo, rest @ ..] = tlvs else {
+            return Err(invalid("Truncated PROXY v2 TLV"))
+        };
+        let (value, rest) = rest.split_at_checked(u16::from_be_bytes([*len_hi, *len_lo]) as usize)
+            .ok_or_else(|| invalid("Truncated PROXY v2 TLV"))?;
+        parsed.push((*kind, Box::from(value)));
+        tlvs = rest;
+    }
+
+    Ok(ProxyInfo {
+        source:      addrs.map(|(src, _)| src),
+        destination: addrs.map(|(_, dst)| dst),
+        tlvs:        parsed,
+    })
+}
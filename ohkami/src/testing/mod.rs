@@ -55,12 +55,22 @@ impl TestingOhkami {
         let router = self.0.clone();
         
         let res = async move {
-            let mut request = Request::init(#[cfg(feature="__rt_native__")] crate::util::IP_0000);
+            let mut request = Request::init(#[cfg(feature="__rt_native__")] crate::util::PEER_0000);
             let mut request = unsafe {Pin::new_unchecked(&mut request)};
             
-            let res = match request.as_mut().read(&mut &req.encode()[..]).await {
+            let res = match request.as_mut().read(
+                &mut &req.encode()[..],
+                #[cfg(feature="__rt_native__")] &crate::ServerConfig::default(),
+            ).await {
+                /* panics are left to the test unless `on_panic` is registered */
+                Ok(Some(())) if router.panic_hook.is_some() => router.handle_catching(&mut request).await,
                 Ok(Some(())) => router.handle(&mut request).await,
                 Ok(None) => panic!("No request"),
+                #[cfg(feature="__rt_native__")]
+                Err(crate::request::ReadError::Respond(res)) => *res,
+                #[cfg(feature="__rt_native__")]
+                Err(crate::request::ReadError::Io(e)) => panic!("Failed to read request: {e}"),
+                #[cfg(not(feature="__rt_native__"))]
                 Err(res) => res,
             };
 
@@ -81,7 +91,7 @@ pub struct Oneshot(
 }
 
 pub struct TestRequest {
-    method:  Method,
+    method:  &'static str,
     path:    Cow<'static, str>,
     queries: HashMap<Cow<'static, str>, Cow<'static, str>>,
     headers: HashMap<Cow<'static, str>, Cow<'static, str>>,
@@ -105,7 +115,7 @@ pub struct TestRequest {
             });
 
         [
-            method.as_str().as_bytes(), b" ", path.as_bytes(), &queries, b" HTTP/1.1\r\n",
+            method.as_bytes(), b" ", path.as_bytes(), &queries, b" HTTP/1.1\r\n",
             &headers,
             b"\r\n",
             &content.unwrap_or(Cow::Borrowed(b""))
@@ -119,7 +129,7 @@ macro_rules! new_test_request {
         impl TestRequest {
             pub fn $method(path: impl Into<Cow<'static, str>>) -> Self {
                 Self {
-                    method:  Method::$method,
+                    method:  Method::$method.as_str(),
                     path:    path.into(),
                     queries: HashMap::new(),
                     headers: HashMap::new(),
@@ -132,6 +142,19 @@ macro_rules! new_test_request {
     GET PUT POST PATCH DELETE HEAD OPTIONS
 }
 
+impl TestRequest {
+    /// Request of any method including an extension one like `PROPFIND`
+    pub fn method(method: &'static str, path: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            method,
+            path:    path.into(),
+            queries: HashMap::new(),
+            headers: HashMap::new(),
+            content: None,
+        }
+    }
+}
+
 impl TestRequest {
     pub fn query(mut self, key: impl Into<Cow<'static, str>>, value: impl Into<Cow<'static, str>>) -> Self {
         self.queries.insert(key.into(), value.into());
@@ -0,0 +1,204 @@
+#![cfg(all(feature="rt_tokio", feature="DEBUG"))]
+
+use super::{TlsConfig, TlsInfo};
+use crate::prelude::*;
+use crate::ohkami::_test_util::{vacant_port, serve_in_background, connect};
+use std::sync::Arc;
+use std::path::PathBuf;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use rustls::pki_types::{CertificateDer, ServerName};
+
+
+struct SelfSigned {
+    der:      CertificateDer<'static>,
+    cert_pem: PathBuf,
+    key_pem:  PathBuf,
+}
+impl SelfSigned {
+    fn generate(server_name: &str) -> Self {
+        let rcgen::CertifiedKey { cert, key_pair } = rcgen::generate_simple_self_signed(
+            vec![server_name.to_string()]
+        ).unwrap();
+
+        let dir = std::env::temp_dir().join(format!("ohkami-tls-test-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+
+        let cert_pem = dir.join(format!("{server_name}.pem"));
+        let key_pem  = dir.join(format!("{server_name}.key"));
+        std::fs::write(&cert_pem, cert.pem()).unwrap();
+        std::fs::write(&key_pem,  key_pair.serialize_pem()).unwrap();
+
+        Self { der: cert.der().clone(), cert_pem, key_pem }
+    }
+}
+
+async fn request_over_tls(
+    port:        u16,
+    server_name: &'static str,
+    trusted:     &[&SelfSigned],
+) -> String {
+    let mut roots = rustls::RootCertStore::empty();
+    for cert in trusted {
+        roots.add(cert.der.clone()).unwrap();
+    }
+    let client_config = rustls::ClientConfig::builder_with_provider(
+        Arc::new(rustls::crypto::ring::default_provider())
+    )
+        .with_safe_default_protocol_versions().unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+
+    let tcp = connect(port).await;
+    let mut tls = connector.connect(ServerName::try_from(server_name).unwrap(), tcp).await
+        .expect("TLS handshake failed");
+
+    tls.write_all(b"GET /hello HTTP/1.1\r\nConnection: close\r\n\r\n").await.unwrap();
+
+    let mut response = Vec::new();
+    let mut buf = [0; 1024];
+    loop {
+        match tls.read(&mut buf).await {
+            Ok(0) | Err(_) => break,
+            Ok(n) => response.extend_from_slice(&buf[..n]),
+        }
+    }
+    String::from_utf8(response).unwrap()
+}
+
+async fn hello(tls: &TlsInfo) -> String {
+    format!("Hello, {}!", tls.server_name().unwrap_or("anonymous"))
+}
+
+fn howls_in_background(port: u16, tls_config: TlsConfig) {
+    serve_in_background(move || Ohkami::new((
+        "/hello".GET(hello),
+    )).howls(("127.0.0.1", port), tls_config));
+}
+
+#[crate::__rt__::test] async fn test_howls_with_sni() {
+    let a = SelfSigned::generate("a.localhost");
+    let b = SelfSigned::generate("b.localhost");
+
+    let tls_config = TlsConfig::builder()
+        .cert(&a.cert_pem, &a.key_pem).unwrap()
+        .sni("b.localhost", &b.cert_pem, &b.key_pem).unwrap()
+        .build();
+
+    let port = vacant_port();
+    howls_in_background(port, tls_config);
+
+    let res = request_over_tls(port, "a.localhost", &[&a]).await;
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\nHello, a.localhost!"), "{res}");
+
+    /* `b.localhost` is served with its own certificate, so trusting only `b` is enough */
+    let res = request_over_tls(port, "b.localhost", &[&b]).await;
+    assert!(res.starts_with("HTTP/1.1 200 OK\r\n"), "{res}");
+    assert!(res.ends_with("\r\n\r\nHello, b.localhost!"), "{res}");
+}
+
+#[crate::__rt__::test] async fn test_howls_from_pem_files() {
+    let c = SelfSigned::generate("c.localhost");
+
+    let tls_config = TlsConfig::from_pem_files(&c.cert_pem, &c.key_pem).unwrap();
+
+    let port = vacant_port();
+    howls_in_background(port, tls_config);
+
+    let res = request_over_tls(port, "c.localhost", &[&c]).await;
+    assert!(res.ends_with("\r\n\r\nHello, c.localhost!"), "{res}");
+}
+
+#[test] fn test_load_invalid_pem() {
+    let dir = std::env::temp_dir().join(format!("ohkami-tls-test-{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let empty = dir.join("empty.pem");
+    std::fs::write(&empty, "").unwrap();
+
+    let err = TlsConfig::from_pem_files(&empty, &empty).err().unwrap();
+    assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+}
+
+#[cfg(feature="http2")]
+#[crate::__rt__::test] async fn test_howls_http2_by_alpn() {
+    let d = SelfSigned::generate("d.localhost");
+
+    let tls_config = TlsConfig::from_pem_files(&d.cert_pem, &d.key_pem).unwrap();
+
+    let port = vacant_port();
+    howls_in_background(port, tls_config);
+
+    let mut roots = rustls::RootCertStore::empty();
+    roots.add(d.der.clone()).unwrap();
+    let mut client_config = rustls::ClientConfig::builder_with_provider(
+        Arc::new(rustls::crypto::ring::default_provider())
+    )
+        .with_safe_default_protocol_versions().unwrap()
+        .with_root_certificates(roots)
+        .with_no_client_auth();
+    client_config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+    let connector = tokio_rustls::TlsConnector::from(Arc::new(client_config));
+
+    let tcp = connect(port).await;
+    let tls = connector.connect(ServerName::try_from("d.localhost").unwrap(), tcp).await
+        .expect("TLS handshake failed");
+    assert_eq!(tls.get_ref().1.alpn_protocol(), Some(&b"h2"[..]));
+
+    let (mut client, connection) = h2::client::handshake(tls).await.unwrap();
+    tokio::spawn(connection);
+
+    let (response, _) = client.send_request(
+        http::Request::get("https://d.localhost/hello").body(()).unwrap(),
+        true
+    ).unwrap();
+    let (head, mut body) = response.await.unwrap().into_parts();
+    assert_eq!(head.status, 200);
+
+    let mut content = Vec::new();
+    while let Some(data) = body.data().await {
+        content.extend_from_slice(&data.unwrap());
+    }
+    assert_eq!(content, b"Hello, d.localhost!");
+}
+
+#[crate::__rt__::test] async fn test_silent_tls_client() {
+    use crate::ServerConfig;
+    use std::time::Duration;
+
+    let e = SelfSigned::generate("e.localhost");
+
+    /* a client sending no ClientHello doesn't hold `max_connections` beyond `header_read_timeout` */
+    let config = ServerConfig::new()
+        .tls(TlsConfig::from_pem_files(&e.cert_pem, &e.key_pem).unwrap())
+        .max_connections(1)
+        .header_read_timeout(Duration::from_millis(300));
+    let port = vacant_port();
+    serve_in_background(move || Ohkami::new((
+        "/hello".GET(hello),
+    )).howl_with(config, ("127.0.0.1", port)));
+
+    let _silent = connect(port).await;
+    let res = crate::util::timeout_in(Duration::from_secs(3), request_over_tls(port, "e.localhost", &[&e])).await
+        .expect("the silent client should be dropped by `header_read_timeout`");
+    assert!(res.ends_with("\r\n\r\nHello, e.localhost!"), "{res}");
+
+    /* nor delays the shutdown beyond `shutdown_timeout` */
+    let config = ServerConfig::new()
+        .tls(TlsConfig::from_pem_files(&e.cert_pem, &e.key_pem).unwrap())
+        .shutdown_timeout(Duration::from_millis(100));
+    let port = vacant_port();
+    let (shutdown_tx, shutdown_rx) = tokio::sync::oneshot::channel::<()>();
+    let serving = serve_in_background(move || Ohkami::new((
+        "/hello".GET(hello),
+    )).howl_with_shutdown(config, ("127.0.0.1", port), async {let _ = shutdown_rx.await;}));
+
+    let _silent = connect(port).await;
+    crate::__rt__::sleep(Duration::from_millis(100)).await;
+    shutdown_tx.send(()).unwrap();
+    crate::util::timeout_in(Duration::from_secs(3), async {
+        while !serving.is_finished() {
+            crate::__rt__::sleep(Duration::from_millis(10)).await
+        }
+    }).await.expect("`howl_with_shutdown` should return after `shutdown_timeout`");
+}
@@ -0,0 +1,87 @@
+#![cfg(feature="rt_tokio")]
+
+//! `futures-rustls` over tokio's I/O, not to depend on
+//! one more TLS adapter for each runtime
+
+use std::{io, pin::Pin, sync::Arc, task::{Context, Poll, ready}};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+
+
+/// tokio's I/O as futures'
+pub(crate) struct Compat<IO>(IO);
+const _: () = {
+    impl<IO: AsyncRead + Unpin> futures_util::io::AsyncRead for Compat<IO> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut [u8]) -> Poll<io::Result<usize>> {
+            let mut buf = ReadBuf::new(buf);
+            ready!(Pin::new(&mut self.get_mut().0).poll_read(cx, &mut buf))?;
+            Poll::Ready(Ok(buf.filled().len()))
+        }
+    }
+
+    impl<IO: AsyncWrite + Unpin> futures_util::io::AsyncWrite for Compat<IO> {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            Pin::new(&mut self.get_mut().0).poll_write(cx, buf)
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().0).poll_flush(cx)
+        }
+        #[inline]
+        fn poll_close(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            Pin::new(&mut self.get_mut().0).poll_shutdown(cx)
+        }
+    }
+};
+
+#[derive(Clone)]
+pub(crate) struct TlsAcceptor(futures_rustls::TlsAcceptor);
+const _: () = {
+    impl From<Arc<rustls::ServerConfig>> for TlsAcceptor {
+        fn from(config: Arc<rustls::ServerConfig>) -> Self {
+            Self(futures_rustls::TlsAcceptor::from(config))
+        }
+    }
+
+    impl TlsAcceptor {
+        pub(crate) async fn accept<IO: AsyncRead + AsyncWrite + Unpin>(&self, io: IO) -> io::Result<TlsStream<IO>> {
+            self.0.accept(Compat(io)).await.map(TlsStream)
+        }
+    }
+};
+
+pub struct TlsStream<IO>(futures_rustls::server::TlsStream<Compat<IO>>);
+const _: () = {
+    impl<IO> TlsStream<IO> {
+        /// The underlying I/O and the rustls session
+        pub fn get_ref(&self) -> (&IO, &rustls::ServerConnection) {
+            let (Compat(io), connection) = self.0.get_ref();
+            (io, connection)
+        }
+    }
+
+    impl<IO: AsyncRead + AsyncWrite + Unpin> AsyncRead for TlsStream<IO> {
+        fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+            use futures_util::io::AsyncRead;
+
+            let n = ready!(Pin::new(&mut self.get_mut().0).poll_read(cx, buf.initialize_unfilled()))?;
+            buf.advance(n);
+            Poll::Ready(Ok(()))
+        }
+    }
+
+    impl<IO: AsyncRead + AsyncWrite + Unpin> AsyncWrite for TlsStream<IO> {
+        #[inline]
+        fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+            futures_util::io::AsyncWrite::poll_write(Pin::new(&mut self.get_mut().0), cx, buf)
+        }
+        #[inline]
+        fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            futures_util::io::AsyncWrite::poll_flush(Pin::new(&mut self.get_mut().0), cx)
+        }
+        #[inline]
+        fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+            futures_util::io::AsyncWrite::poll_close(Pin::new(&mut self.get_mut().0), cx)
+        }
+    }
+};
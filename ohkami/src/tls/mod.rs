@@ -0,0 +1,230 @@
+#![cfg(all(feature="tls", feature="__rt_native__"))]
+
+//! TLS ( HTTPS ) support by [rustls](https://crates.io/crates/rustls)
+//!
+//! <br>
+//!
+//! *example.rs*
+//! ```no_run
+//! use ohkami::prelude::*;
+//! use ohkami::tls::{TlsConfig, TlsInfo};
+//!
+//! async fn hello(tls: &TlsInfo) -> String {
+//!     format!("Hello, {}!", tls.server_name().unwrap_or("anonymous"))
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let tls_config = TlsConfig::builder()
+//!         .cert("certs/default.pem", "certs/default.key").unwrap()
+//!         .sni("api.example.com", "certs/api.pem", "certs/api.key").unwrap()
+//!         .build();
+//!
+//!     Ohkami::new((
+//!         "/".GET(hello),
+//!     )).howls("0.0.0.0:443", tls_config).await
+//! }
+//! ```
+
+#[cfg(test)]
+mod _test;
+
+mod compat;
+#[cfg(feature="rt_tokio")]
+pub(crate) use compat::TlsAcceptor;
+#[cfg(not(feature="rt_tokio"))]
+pub(crate) use futures_rustls::TlsAcceptor;
+
+/// TLS stream of a connection served by `Ohkami::howls`
+#[cfg(feature="rt_tokio")]
+pub use compat::TlsStream;
+/// TLS stream of a connection served by `Ohkami::howls`
+#[cfg(not(feature="rt_tokio"))]
+pub use futures_rustls::server::TlsStream;
+
+pub use ::rustls;
+
+use std::{io, path::Path, sync::Arc, collections::HashMap};
+use rustls::{sign::CertifiedKey, server::{ClientHello, ResolvesServerCert}};
+use rustls_pki_types::{CertificateDer, PrivateKeyDer, pem::PemObject};
+use crate::{FromRequest, Request};
+
+
+/// # TLS configuration for `Ohkami::howls`
+///
+/// - `TlsConfig::from_pem_files` for a single certificate
+/// - `TlsConfig::builder` for SNI-based certificate selection
+/// - `From<rustls::ServerConfig>` for full control
+#[derive(Clone)]
+pub struct TlsConfig(
+    pub(crate) Arc<rustls::ServerConfig>
+);
+
+impl TlsConfig {
+    /// Load a PEM-encoded certificate chain and private key
+    /// and serve them for any server name.
+    pub fn from_pem_files(
+        cert_path: impl AsRef<Path>,
+        key_path:  impl AsRef<Path>,
+    ) -> io::Result<Self> {
+        Ok(Self::builder().cert(cert_path, key_path)?.build())
+    }
+
+    pub fn builder() -> TlsConfigBuilder {
+        TlsConfigBuilder {
+            default: None,
+            by_name: HashMap::new(),
+            alpn:    vec![
+                #[cfg(feature="http2")]
+                b"h2".to_vec(),
+                b"http/1.1".to_vec(),
+            ],
+        }
+    }
+
+    pub(crate) fn acceptor(&self) -> crate::__rt__::TlsAcceptor {
+        crate::__rt__::TlsAcceptor::from(Arc::clone(&self.0))
+    }
+}
+
+impl From<rustls::ServerConfig> for TlsConfig {
+    fn from(config: rustls::ServerConfig) -> Self {
+        Self(Arc::new(config))
+    }
+}
+impl From<Arc<rustls::ServerConfig>> for TlsConfig {
+    fn from(config: Arc<rustls::ServerConfig>) -> Self {
+        Self(config)
+    }
+}
+
+pub struct TlsConfigBuilder {
+    default: Option<Arc<CertifiedKey>>,
+    by_name: HashMap<String, Arc<CertifiedKey>>,
+    alpn:    Vec<Vec<u8>>,
+}
+impl TlsConfigBuilder {
+    /// Certificate used when the client sends no SNI,
+    /// or sends a server name not registered by `.sni`.
+    pub fn cert(mut self,
+        cert_path: impl AsRef<Path>,
+        key_path:  impl AsRef<Path>,
+    ) -> io::Result<Self> {
+        self.default = Some(Arc::new(load_certified_key(cert_path.as_ref(), key_path.as_ref())?));
+        Ok(self)
+    }
+
+    /// Certificate used when the client requests `server_name` via SNI.
+    pub fn sni(mut self,
+        server_name: &str,
+        cert_path:   impl AsRef<Path>,
+        key_path:    impl AsRef<Path>,
+    ) -> io::Result<Self> {
+        self.by_name.insert(
+            server_name.to_ascii_lowercase(),
+            Arc::new(load_certified_key(cert_path.as_ref(), key_path.as_ref())?)
+        );
+        Ok(self)
+    }
+
+    /// ALPN protocols to offer. Default: `["http/1.1"]`,
+    /// or `["h2", "http/1.1"]` with `http2` feature
+    pub fn alpn<const N: usize>(mut self, protocols: [&'static str; N]) -> Self {
+        self.alpn = protocols.into_iter().map(|p| p.as_bytes().to_vec()).collect();
+        self
+    }
+
+    pub fn build(self) -> TlsConfig {
+        let Self { default, by_name, alpn } = self;
+
+        let mut config = rustls::ServerConfig::builder_with_provider(
+            Arc::new(rustls::crypto::ring::default_provider())
+        )
+            .with_safe_default_protocol_versions().expect("ring provider supports default protocol versions")
+            .with_no_client_auth()
+            .with_cert_resolver(Arc::new(SniResolver { default, by_name }));
+        config.alpn_protocols = alpn;
+
+        TlsConfig(Arc::new(config))
+    }
+}
+
+#[derive(Debug)]
+struct SniResolver {
+    default: Option<Arc<CertifiedKey>>,
+    by_name: HashMap<String, Arc<CertifiedKey>>,
+}
+impl ResolvesServerCert for SniResolver {
+    fn resolve(&self, client_hello: ClientHello<'_>) -> Option<Arc<CertifiedKey>> {
+        client_hello.server_name()
+            .and_then(|name| self.by_name.get(&name.to_ascii_lowercase()))
+            .or(self.default.as_ref())
+            .cloned()
+    }
+}
+
+fn load_certified_key(cert_path: &Path, key_path: &Path) -> io::Result<CertifiedKey> {
+    fn invalid_data(path: &Path, e: impl std::fmt::Display) -> io::Error {
+        io::Error::new(io::ErrorKind::InvalidData, format!("{}: {e}", path.display()))
+    }
+
+    let certs = CertificateDer::pem_file_iter(cert_path)
+        .map_err(|e| invalid_data(cert_path, e))?
+        .collect::<Result<Vec<_>, _>>()
+        .map_err(|e| invalid_data(cert_path, e))?;
+    if certs.is_empty() {
+        return Err(invalid_data(cert_path, "no certificate found"))
+    }
+
+    let key = PrivateKeyDer::from_pem_file(key_path)
+        .map_err(|e| invalid_data(key_path, e))?;
+    let key = rustls::crypto::ring::sign::any_supported_type(&key)
+        .map_err(|e| invalid_data(key_path, e))?;
+
+    Ok(CertifiedKey::new(certs, key))
+}
+
+
+/// Information of the TLS session negotiated on the connection
+///
+/// Available in handlers as `&TlsInfo` ( only for connections accepted by `howls` ).
+#[derive(Clone, Debug)]
+pub struct TlsInfo {
+    server_name:      Option<String>,
+    alpn_protocol:    Option<Vec<u8>>,
+    protocol_version: Option<rustls::ProtocolVersion>,
+    cipher_suite:     Option<rustls::CipherSuite>,
+}
+impl TlsInfo {
+    pub(crate) fn of(connection: &rustls::ServerConnection) -> Self {
+        Self {
+            server_name:      connection.server_name().map(String::from),
+            alpn_protocol:    connection.alpn_protocol().map(<[u8]>::to_vec),
+            protocol_version: connection.protocol_version(),
+            cipher_suite:     connection.negotiated_cipher_suite().map(|cs| cs.suite()),
+        }
+    }
+
+    /// Server name requested by the client via SNI
+    pub fn server_name(&self) -> Option<&str> {
+        self.server_name.as_deref()
+    }
+    /// Protocol negotiated via ALPN
+    pub fn alpn_protocol(&self) -> Option<&[u8]> {
+        self.alpn_protocol.as_deref()
+    }
+    pub fn protocol_version(&self) -> Option<rustls::ProtocolVersion> {
+        self.protocol_version
+    }
+    pub fn cipher_suite(&self) -> Option<rustls::CipherSuite> {
+        self.cipher_suite
+    }
+}
+
+impl<'req> FromRequest<'req> for &'req TlsInfo {
+    type Error = std::convert::Infallible;
+    #[inline]
+    fn from_request(req: &'req Request) -> Option<Result<Self, Self::Error>> {
+        req.tls().map(Ok)
+    }
+}
@@ -87,40 +87,51 @@ const _: () = {
 pub fn timeout_in<T>(
     duration: std::time::Duration,
     proc:     impl std::future::Future<Output = T>
+) -> impl std::future::Future<Output = Option<T>> {
+    until(crate::__rt__::sleep(duration), proc)
+}
+
+/// Run `proc`, or give up with `None` when `stop` completes first
+#[cfg(feature="__rt_native__")]
+pub(crate) fn until<T>(
+    stop: impl std::future::Future<Output = ()>,
+    proc: impl std::future::Future<Output = T>
 ) -> impl std::future::Future<Output = Option<T>> {
     use std::task::Poll;
     use std::pin::Pin;
 
-    struct Timeout<Sleep, Proc> { sleep: Sleep, proc: Proc }
+    struct Until<Stop, Proc> { stop: Stop, proc: Proc }
 
-    impl<Sleep, Proc, T> std::future::Future for Timeout<Sleep, Proc>
+    impl<Stop, Proc, T> std::future::Future for Until<Stop, Proc>
     where
-        Sleep: std::future::Future<Output = ()>,
+        Stop:  std::future::Future<Output = ()>,
         Proc:  std::future::Future<Output = T>,
     {
         type Output = Option<T>;
 
         #[inline]
         fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-            let Timeout { sleep, proc } = unsafe {self.get_unchecked_mut()};
+            let Until { stop, proc } = unsafe {self.get_unchecked_mut()};
             match unsafe {Pin::new_unchecked(proc)}.poll(cx) {
                 Poll::Ready(t) => Poll::Ready(Some(t)),
-                Poll::Pending  => unsafe {Pin::new_unchecked(sleep)}.poll(cx).map(|_| None)
+                Poll::Pending  => unsafe {Pin::new_unchecked(stop)}.poll(cx).map(|_| None)
             }
         }
     }
 
     #[cfg(feature="rt_glommio")]
     /* for fang::builtin::timeout::Timeout::Proc::bite to return Send Future */
-    /* SAFETY: proc and sleep are executed on the same thread in rt_glommio */
+    /* SAFETY: proc and stop are executed on the same thread in rt_glommio */
     /* ( glommio::timer::sleep itself returns not-Send Future because it's not needed due to the architecture ) */
-    unsafe impl<Sleep, Proc> Send for Timeout<Sleep, Proc> {}
+    unsafe impl<Stop, Proc> Send for Until<Stop, Proc> {}
 
-    Timeout { proc, sleep: crate::__rt__::sleep(duration) }
+    Until { proc, stop }
 }
 
 #[cfg(feature="__rt_native__")]
 pub const IP_0000: std::net::IpAddr = std::net::IpAddr::V4(std::net::Ipv4Addr::new(0, 0, 0, 0));
+#[cfg(feature="__rt_native__")]
+pub(crate) const PEER_0000: crate::request::PeerAddr = crate::request::PeerAddr::Tcp(std::net::SocketAddr::new(IP_0000, 0));
 
 #[cfg(feature="rt_glommio")]
 pub use num_cpus;
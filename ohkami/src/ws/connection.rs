@@ -5,7 +5,7 @@ use crate::__rt__::{AsyncWriter, AsyncReader};
 
 
 /// WebSocket connection
-pub struct Connection<Conn: AsyncWriter + AsyncReader + Unpin> {
+pub struct Connection<Conn: AsyncWriter + AsyncReader + Unpin = super::RawConnection> {
     conn:       Arc<UnsafeCell<(State, Conn)>>,
     config:     Config,
     n_buffered: usize,
@@ -149,15 +149,32 @@ impl<Conn: AsyncWriter + AsyncReader + Unpin> Connection<Conn> {
 #[cfg(feature="rt_tokio")]
 pub mod split {
     use super::*;
-    use tokio::net::{TcpStream, tcp::{ReadHalf as TcpReadHalf, WriteHalf as TcpWriteHalf}};
+    use super::super::RawConnection;
+    use std::{io, pin::Pin, task::{Context, Poll}};
+    use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
 
 
-    impl Connection<TcpStream> {
+    impl Connection<RawConnection> {
         pub fn split(self) -> (ReadHalf, WriteHalf) {
             let (state, conn) = unsafe {&mut *self.conn.get()};
             state.assert_alive();
-    
-            let (read, write) = conn.split();
+
+            let (read, write) = match conn {
+                RawConnection::Tcp(tcp) => {
+                    let (read, write) = tcp.split();
+                    (RawReadHalf::Tcp(read), RawWriteHalf::Tcp(write))
+                }
+                #[cfg(unix)]
+                RawConnection::Unix(unix) => {
+                    let (read, write) = unix.split();
+                    (RawReadHalf::Unix(read), RawWriteHalf::Unix(write))
+                }
+                #[cfg(feature="tls")]
+                RawConnection::Tls(tls) => {
+                    let (read, write) = tokio::io::split(&mut **tls);
+                    (RawReadHalf::Tls(read), RawWriteHalf::Tls(write))
+                }
+            };
             (
                 ReadHalf  { conn: read,  config: self.config.clone() },
                 WriteHalf { conn: write, config: self.config, n_buffered: self.n_buffered }
@@ -165,8 +182,61 @@ pub mod split {
         }
     }
 
+    /// Halves of a plain connection borrow it without locking;
+    /// only a TLS stream has to be shared between them.
+    enum RawReadHalf {
+        Tcp(tokio::net::tcp::ReadHalf<'static>),
+        #[cfg(unix)]
+        Unix(tokio::net::unix::ReadHalf<'static>),
+        #[cfg(feature="tls")]
+        Tls(tokio::io::ReadHalf<&'static mut crate::__rt__::TlsStream<crate::__rt__::TcpStream>>),
+    }
+    enum RawWriteHalf {
+        Tcp(tokio::net::tcp::WriteHalf<'static>),
+        #[cfg(unix)]
+        Unix(tokio::net::unix::WriteHalf<'static>),
+        #[cfg(feature="tls")]
+        Tls(tokio::io::WriteHalf<&'static mut crate::__rt__::TlsStream<crate::__rt__::TcpStream>>),
+    }
+
+    macro_rules! dispatch {
+        ($self:ident : $Half:ident => |$half:ident| $proc:expr) => {
+            match $self.get_mut() {
+                $Half::Tcp($half) => {let $half = Pin::new($half); $proc}
+                #[cfg(unix)]
+                $Half::Unix($half) => {let $half = Pin::new($half); $proc}
+                #[cfg(feature="tls")]
+                $Half::Tls($half) => {let $half = Pin::new($half); $proc}
+            }
+        };
+    }
+
+    const _: () = {
+        impl AsyncRead for RawReadHalf {
+            #[inline]
+            fn poll_read(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &mut ReadBuf<'_>) -> Poll<io::Result<()>> {
+                dispatch!(self: RawReadHalf => |half| half.poll_read(cx, buf))
+            }
+        }
+
+        impl AsyncWrite for RawWriteHalf {
+            #[inline]
+            fn poll_write(self: Pin<&mut Self>, cx: &mut Context<'_>, buf: &[u8]) -> Poll<io::Result<usize>> {
+                dispatch!(self: RawWriteHalf => |half| half.poll_write(cx, buf))
+            }
+            #[inline]
+            fn poll_flush(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                dispatch!(self: RawWriteHalf => |half| half.poll_flush(cx))
+            }
+            #[inline]
+            fn poll_shutdown(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+                dispatch!(self: RawWriteHalf => |half| half.poll_shutdown(cx))
+            }
+        }
+    };
+
     pub struct ReadHalf {
-        conn:   TcpReadHalf<'static>,
+        conn:   RawReadHalf,
         config: Config,
     }
     impl ReadHalf {
@@ -179,7 +249,7 @@ pub mod split {
     }
 
     pub struct WriteHalf {
-        conn:       TcpWriteHalf<'static>,
+        conn:       RawWriteHalf,
         config:     Config,
         n_buffered: usize,
     }
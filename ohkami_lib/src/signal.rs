@@ -1,2 +1,59 @@
 pub use ::tokio::signal::ctrl_c;
 pub use ::tokio::sync::watch;
+pub use ::tokio::sync::Semaphore;
+pub use ::tokio::sync::OwnedSemaphorePermit;
+
+/// Completes when the process receives SIGINT ( Ctrl-C ) or SIGTERM,
+/// or Ctrl-C on non-unix platforms.
+/// 
+/// Unlike `tokio::signal`, this doesn't require tokio runtime
+/// and works on any async runtime.
+/// 
+/// Only the first signal is caught : a second one terminates
+/// the process as usual, without waiting for whatever this triggered.
+pub async fn shutdown() {
+    use std::sync::OnceLock;
+
+    static RECEIVED: OnceLock<watch::Receiver<bool>> = OnceLock::new();
+
+    let mut received = RECEIVED.get_or_init(|| {
+        let (tx, rx) = watch::channel(false);
+
+        #[cfg(unix)] {
+            use signal_hook::{consts::{SIGINT, SIGTERM}, iterator::Signals};
+
+            let mut signals = Signals::new([SIGINT, SIGTERM]).expect("Failed to register signal handlers");
+            std::thread::spawn(move || {
+                let mut signals = signals.forever();
+                if signals.next().is_some() {
+                    tx.send_replace(true);
+                }
+                for signal in signals {
+                    let _ = signal_hook::low_level::emulate_default_handler(signal);
+                }
+            });
+        }
+
+        #[cfg(not(unix))] {
+            /* `ctrl_c` needs tokio runtime, so waiting on a small one of its own */
+            let runtime = ::tokio::runtime::Builder::new_current_thread().enable_all().build()
+                .expect("Failed to build runtime for Ctrl-C handler");
+            std::thread::spawn(move || runtime.block_on(async {
+                if ctrl_c().await.is_ok() {
+                    tx.send_replace(true);
+                }
+                if ctrl_c().await.is_ok() {
+                    /* as the default handler does ( `STATUS_CONTROL_C_EXIT` ) */
+                    std::process::exit(0xC000013A_u32 as i32)
+                }
+            }));
+        }
+
+        rx
+    }).clone();
+
+    if received.wait_for(|received| *received).await.is_err() {
+        /* the thread has gone without any signal */
+        std::future::pending::<()>().await
+    }
+}